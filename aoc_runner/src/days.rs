@@ -0,0 +1,11 @@
+//! [`Solution`](crate::Solution) implementations for each solved day.
+
+mod day_1;
+mod day_2;
+mod day_3;
+mod day_4;
+
+pub(crate) use day_1::Day1;
+pub(crate) use day_2::Day2;
+pub(crate) use day_3::Day3;
+pub(crate) use day_4::Day4;
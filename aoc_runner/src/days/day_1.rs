@@ -0,0 +1,27 @@
+use anyhow::Result;
+use day_1_calorie_counting::{
+    greatest_calories_carried, parse_elves_calories, total_calories_of_most_snack_ready_elves,
+    ElvesCalories, TotalCalories,
+};
+
+use crate::Solution;
+
+/// Day 1: Calorie Counting
+pub(crate) struct Day1;
+
+impl Solution for Day1 {
+    type Input = ElvesCalories;
+    type Answer = TotalCalories;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_elves_calories(input)?)
+    }
+
+    fn part_1(elves_calories: &Self::Input) -> Result<Self::Answer> {
+        Ok(greatest_calories_carried(elves_calories)?)
+    }
+
+    fn part_2(elves_calories: &Self::Input) -> Result<Self::Answer> {
+        Ok(total_calories_of_most_snack_ready_elves(elves_calories, 3)?)
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use day_2_rock_paper_scissors::{
+    parse_moves_strategy_guide, parse_outcomes_strategy_guide, rounds_from_outcomes, total_score,
+    Round, Score,
+};
+
+use crate::Solution;
+
+/// Day 2: Rock Paper Scissors
+pub(crate) struct Day2;
+
+/// The strategy guide's rounds under both interpretations of its second column.
+pub(crate) struct StrategyGuide {
+    /// Rounds where the second column is the player's move.
+    moves: Vec<Round>,
+    /// Rounds where the second column is the desired outcome.
+    outcomes: Vec<Round>,
+}
+
+impl Solution for Day2 {
+    type Input = StrategyGuide;
+    type Answer = Score;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(StrategyGuide {
+            moves: parse_moves_strategy_guide(input)?,
+            outcomes: rounds_from_outcomes(&parse_outcomes_strategy_guide(input)?),
+        })
+    }
+
+    fn part_1(strategy_guide: &Self::Input) -> Result<Self::Answer> {
+        Ok(total_score(&strategy_guide.moves))
+    }
+
+    fn part_2(strategy_guide: &Self::Input) -> Result<Self::Answer> {
+        Ok(total_score(&strategy_guide.outcomes))
+    }
+}
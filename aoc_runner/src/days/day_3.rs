@@ -0,0 +1,39 @@
+use anyhow::Result;
+use day_3_rucksack_reorganization::{
+    groups_common_items_priority, parse_groups, parse_rucksacks,
+    rucksacks_duplicate_items_priority, Rucksack, TotalPriority,
+};
+
+use crate::Solution;
+
+/// Day 3: Rucksack Reorganization
+pub(crate) struct Day3;
+
+/// The rucksacks on each line of the puzzle input.
+pub(crate) struct Rucksacks {
+    rucksacks: Vec<Rucksack>,
+    /// The raw puzzle input, only split into groups for Part 2, since Part 1 doesn't need a whole number of groups.
+    input: String,
+}
+
+impl Solution for Day3 {
+    type Input = Rucksacks;
+    type Answer = TotalPriority;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Rucksacks {
+            rucksacks: parse_rucksacks(input)?,
+            input: input.to_string(),
+        })
+    }
+
+    fn part_1(rucksacks: &Self::Input) -> Result<Self::Answer> {
+        Ok(rucksacks_duplicate_items_priority(&rucksacks.rucksacks))
+    }
+
+    fn part_2(rucksacks: &Self::Input) -> Result<Self::Answer> {
+        Ok(groups_common_items_priority(&parse_groups(
+            &rucksacks.input,
+        )?))
+    }
+}
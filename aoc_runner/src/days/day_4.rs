@@ -0,0 +1,24 @@
+use anyhow::Result;
+use day_4_camp_cleanup::{fully_redundant_pairs, parse_pairs, partly_redundant_pairs, Pair};
+
+use crate::Solution;
+
+/// Day 4: Camp Cleanup
+pub(crate) struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_pairs(input)?)
+    }
+
+    fn part_1(pairs: &Self::Input) -> Result<Self::Answer> {
+        Ok(fully_redundant_pairs(pairs))
+    }
+
+    fn part_2(pairs: &Self::Input) -> Result<Self::Answer> {
+        Ok(partly_redundant_pairs(pairs))
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+
+use days::{Day1, Day2, Day3, Day4};
+
+mod days;
+
+/// A part of a day's puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+/// Solution to both parts of a day's puzzle.
+///
+/// Each day parses its puzzle input once into `Input`, which both parts are then solved from.
+pub trait Solution {
+    /// Puzzle input parsed into the form both parts are solved from.
+    type Input;
+
+    /// Answer produced by each part of the puzzle.
+    type Answer: Display;
+
+    /// Parse the raw puzzle `input`.
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Returns the answer to Part 1 of the puzzle.
+    fn part_1(input: &Self::Input) -> Result<Self::Answer>;
+
+    /// Returns the answer to Part 2 of the puzzle.
+    fn part_2(input: &Self::Input) -> Result<Self::Answer>;
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(part: u8) -> Result<Self> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => bail!("Puzzles only have parts 1 and 2, not part {part}"),
+        }
+    }
+}
+
+/// Returns the answers to each of the `parts` of a `Solution`'s puzzle from the raw `input`.
+fn answers<S: Solution>(parts: &[Part], input: &str) -> Result<Vec<String>> {
+    let input = S::parse(input)?;
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::One => S::part_1(&input).map(|answer| answer.to_string()),
+            Part::Two => S::part_2(&input).map(|answer| answer.to_string()),
+        })
+        .collect()
+}
+
+/// Returns the answers to each of the `parts` of the puzzle for `day` from the raw `input`.
+pub fn solve(day: u8, parts: &[Part], input: &str) -> Result<Vec<String>> {
+    match day {
+        1 => answers::<Day1>(parts, input),
+        2 => answers::<Day2>(parts, input),
+        3 => answers::<Day3>(parts, input),
+        4 => answers::<Day4>(parts, input),
+        _ => bail!("Day {day} has not been solved"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{solve, Part};
+
+    const BOTH_PARTS: &[Part] = &[Part::One, Part::Two];
+
+    #[test]
+    fn day_1_example() {
+        let input = include_str!("../../day_1_calorie_counting/inputs/day1-example.txt");
+        assert_eq!(solve(1, BOTH_PARTS, input).unwrap(), vec!["24000", "45000"]);
+    }
+
+    #[test]
+    fn day_2_example() {
+        let input = include_str!("../../day_2_rock_paper_scissors/inputs/day2-example.txt");
+        assert_eq!(solve(2, BOTH_PARTS, input).unwrap(), vec!["15", "12"]);
+    }
+
+    #[test]
+    fn day_3_example() {
+        const INPUT: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+        assert_eq!(solve(3, BOTH_PARTS, INPUT).unwrap(), vec!["157", "70"]);
+
+        // Part 1 doesn't split the rucksacks into groups, so it doesn't need a whole number of them.
+        let partial_group = &INPUT[..INPUT.len() - "CrZsJsPPZsGzwwsLwLmpwMDw\n".len()];
+        assert_eq!(solve(3, &[Part::One], partial_group).unwrap(), vec!["138"]);
+        assert!(solve(3, &[Part::Two], partial_group).is_err());
+    }
+
+    #[test]
+    fn day_4_example() {
+        const INPUT: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+        assert_eq!(solve(4, BOTH_PARTS, INPUT).unwrap(), vec!["2", "4"]);
+    }
+
+    #[test]
+    fn single_part() {
+        let input = include_str!("../../day_2_rock_paper_scissors/inputs/day2-example.txt");
+        assert_eq!(solve(2, &[Part::Two], input).unwrap(), vec!["12"]);
+    }
+
+    #[test]
+    fn unsolved_day() {
+        assert!(solve(25, &[Part::One], "").is_err());
+    }
+}
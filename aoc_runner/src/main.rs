@@ -0,0 +1,47 @@
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use aoc_runner::{solve, Part};
+
+/// Solve Advent of Code 2022 puzzles.
+#[derive(Parser)]
+struct Args {
+    /// Day of the puzzle to solve.
+    #[arg(long)]
+    day: u8,
+
+    /// Part of the puzzle to solve; both parts are solved if omitted.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Path to the puzzle input.
+    #[arg(long)]
+    input: PathBuf,
+}
+
+fn run(args: Args) -> Result<()> {
+    let parts = match args.part {
+        Some(part) => vec![Part::try_from(part)?],
+        None => vec![Part::One, Part::Two],
+    };
+    let input = fs::read_to_string(&args.input)
+        .with_context(|| format!("Failed to read input file {}", args.input.display()))?;
+
+    for (part, answer) in parts.iter().zip(solve(args.day, &parts, &input)?) {
+        println!("Day {}, Part {}: {}", args.day, part, answer);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // Print the error on its own (rather than returning it from `main`) so parse errors render cleanly.
+            eprintln!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
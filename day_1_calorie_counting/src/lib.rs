@@ -0,0 +1,305 @@
+mod parser;
+mod report;
+mod stream;
+
+use std::{error::Error, fmt::Display, fs, ops::Deref};
+
+use parse_error::ParseError;
+
+pub use parser::parse_elves_foods_calories;
+pub use report::{Bucket, CaloriesReport, ElfSummary, Food, Percentile};
+pub use stream::{most_snack_ready_from_reader, StreamError, TopElves};
+
+pub static INPUT_FILE: &str = "inputs/day1.txt";
+
+/// A quantity of calories.
+pub type Calories = u32;
+
+/// Total calories of many foods, wide enough that summing even huge inputs doesn't overflow in practice.
+pub type TotalCalories = u64;
+
+/// An elf and the calories of the foods they are carrying.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Elf {
+    /// Position of the elf's block of calories lines in the input (starting from 0).
+    pub position: usize,
+    /// Calories of each food the elf is carrying.
+    pub foods: Vec<Calories>,
+    /// Total calories of all the foods the elf is carrying.
+    pub calories: TotalCalories,
+}
+
+/// List of elves sorted by descending snack-readiness.
+///
+/// Elves carrying the same total calories are ordered by their position in the input.
+#[derive(Debug)]
+pub struct ElvesCalories(Vec<Elf>);
+
+/// Error produced when asking for more of the most snack-ready elves than there are elves.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NotEnoughElves {
+    /// Number of elves that were asked for.
+    pub requested: usize,
+    /// Number of elves there actually are.
+    pub available: usize,
+}
+
+impl Elf {
+    /// Construct an `Elf` at `position` in the input carrying `foods`.
+    pub fn new(position: usize, foods: Vec<Calories>) -> Elf {
+        Elf {
+            position,
+            calories: foods
+                .iter()
+                .map(|&calories| TotalCalories::from(calories))
+                .sum(),
+            foods,
+        }
+    }
+}
+
+impl Deref for ElvesCalories {
+    type Target = Vec<Elf>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ElvesCalories {
+    /// Construct `ElvesCalories` from a list of elves by sorting by descending snack-readiness.
+    pub fn new(mut elves: Vec<Elf>) -> ElvesCalories {
+        // Sort by descending calories, breaking ties by ascending position
+        elves.sort_by(|a, b| {
+            b.calories
+                .cmp(&a.calories)
+                .then(a.position.cmp(&b.position))
+        });
+
+        ElvesCalories(elves)
+    }
+
+    /// Returns some number of `elves` who are carrying the most calories (AKA the most snack-ready).
+    ///
+    /// Returns an error if there are fewer than `elves` elves.
+    pub fn most_snack_ready(&self, elves: usize) -> Result<&[Elf], NotEnoughElves> {
+        self.get(..elves).ok_or(NotEnoughElves {
+            requested: elves,
+            available: self.len(),
+        })
+    }
+
+    /// Returns the elf at `position` in the input, if there is one.
+    pub fn elf(&self, position: usize) -> Option<&Elf> {
+        self.iter().find(|elf| elf.position == position)
+    }
+
+    /// Returns the total calories carried by each elf sorted by descending snack-readiness.
+    pub fn calories(&self) -> Vec<TotalCalories> {
+        self.iter().map(|elf| elf.calories).collect()
+    }
+}
+
+impl Display for NotEnoughElves {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Asked for the {} most snack-ready elves, but there are only {} elves",
+            self.requested, self.available
+        )
+    }
+}
+
+impl Error for NotEnoughElves {}
+
+impl Display for ElvesCalories {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for elf in self.iter() {
+            writeln!(f, "{} | elf {}", elf.calories, elf.position)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<Vec<Calories>>> for ElvesCalories {
+    fn from(elves_foods_calories: Vec<Vec<Calories>>) -> Self {
+        // Track each elf by the position of what they recorded in the input, summing up the calories of
+        // their foods to get the total calories they carry.
+        let elves = elves_foods_calories
+            .into_iter()
+            .enumerate()
+            .map(|(position, elf_foods_calories)| Elf::new(position, elf_foods_calories))
+            .collect();
+
+        ElvesCalories::new(elves)
+    }
+}
+
+/// Returns [`ElvesCalories`] parsed from input data in `input_file`.
+pub fn elves_calories(input_file: &str) -> ElvesCalories {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    parse_elves_calories(&input).unwrap_or_else(|e| panic!("Failed to parse input file\n{}", e))
+}
+
+/// Returns [`ElvesCalories`] parsed from `input`.
+pub fn parse_elves_calories(input: &str) -> Result<ElvesCalories, ParseError> {
+    // Parse the input into a list of what each elf recorded (a list of calories of foods they are carrying).
+    let elves_foods_calories: Vec<Vec<Calories>> = parse_elves_foods_calories(input)?;
+
+    Ok(ElvesCalories::from(elves_foods_calories))
+}
+
+/// Returns the greatest calories carried by an elf calculated from the data in `input_file`.
+///
+/// This produces the solution to Day 1 Part 1.
+pub fn greatest_calories_carried(
+    elves_calories: &ElvesCalories,
+) -> Result<TotalCalories, NotEnoughElves> {
+    // Return the calories carried by the single most best snack-ready elf
+    Ok(elves_calories.most_snack_ready(1_usize)?[0].calories)
+}
+
+/// Returns the total sum of the calories carried by the most snack-ready `number_of_elves`.
+///
+/// The most snack-ready elves are the elves carrying the most total calories compared to other elves.
+///
+/// For example, passing `3` for `number_of_elves` returns the total sum of calories carried by the 3 elves
+/// who are carrying the most calories individually compared to the other elves.
+///
+/// This produces the solution to Day 1 Part 2.
+pub fn total_calories_of_most_snack_ready_elves(
+    elves_calories: &ElvesCalories,
+    elves: usize,
+) -> Result<TotalCalories, NotEnoughElves> {
+    Ok(elves_calories
+        .most_snack_ready(elves)?
+        .iter()
+        .map(|elf| elf.calories)
+        .sum())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        elves_calories, greatest_calories_carried, total_calories_of_most_snack_ready_elves,
+        Calories, Elf, ElvesCalories, NotEnoughElves, TotalCalories, INPUT_FILE,
+    };
+
+    static EXAMPLE_INPUT_FILE: &str = "inputs/day1-example.txt";
+
+    #[test]
+    fn day_1_part_1_example() {
+        let elves_calories = elves_calories(EXAMPLE_INPUT_FILE);
+        assert_eq!(greatest_calories_carried(&elves_calories).unwrap(), 24000);
+    }
+
+    #[test]
+    fn solve_day_1_part_1() {
+        let elves_calories = elves_calories(INPUT_FILE);
+        assert_eq!(greatest_calories_carried(&elves_calories).unwrap(), 67658);
+    }
+
+    #[test]
+    fn day_1_part_2_example() {
+        let elves_calories = elves_calories(EXAMPLE_INPUT_FILE);
+        assert_eq!(
+            total_calories_of_most_snack_ready_elves(&elves_calories, 3).unwrap(),
+            45000
+        );
+    }
+
+    #[test]
+    fn solve_day_1_part_2() {
+        let elves_calories = elves_calories(INPUT_FILE);
+        assert_eq!(
+            total_calories_of_most_snack_ready_elves(&elves_calories, 3).unwrap(),
+            200158
+        );
+    }
+
+    #[test]
+    fn elves_calories_is_sorted() {
+        let unsorted_elves_calories: Vec<Calories> = vec![2, 1, 7, 3, 0, 5, 9, 4, 6, 8];
+
+        let elves_calories = ElvesCalories::new(
+            unsorted_elves_calories
+                .into_iter()
+                .enumerate()
+                .map(|(position, calories)| Elf::new(position, vec![calories]))
+                .collect(),
+        );
+
+        assert_eq!(
+            elves_calories.calories(),
+            vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn elves_calories_from_elves_foods_calories() {
+        let elves_foods_calories: Vec<Vec<Calories>> =
+            vec![vec![100, 200], vec![7, 3], vec![10000]];
+
+        let elves_calories = ElvesCalories::from(elves_foods_calories);
+
+        assert_eq!(elves_calories.calories(), vec![10000, 300, 10]);
+    }
+
+    #[test]
+    fn elves_keep_their_identity() {
+        let elves_calories = ElvesCalories::from(vec![
+            vec![100, 200],
+            vec![7, 3],
+            vec![10000],
+            vec![150, 150],
+        ]);
+
+        let top_positions: Vec<usize> = elves_calories
+            .most_snack_ready(3)
+            .unwrap()
+            .iter()
+            .map(|elf| elf.position)
+            .collect();
+        // Elves 0 and 3 both carry 300 calories, so the tie is broken by input position.
+        assert_eq!(top_positions, vec![2, 0, 3]);
+
+        assert_eq!(
+            elves_calories.elf(1),
+            Some(&Elf {
+                position: 1,
+                foods: vec![7, 3],
+                calories: 10
+            })
+        );
+        assert_eq!(elves_calories.elf(4), None);
+    }
+
+    #[test]
+    fn elf_calories_beyond_food_calories() {
+        let elf = Elf::new(0, vec![Calories::MAX, Calories::MAX]);
+
+        assert_eq!(elf.calories, 2 * TotalCalories::from(Calories::MAX));
+    }
+
+    #[test]
+    fn most_snack_ready_out_of_range() {
+        let elves_calories = ElvesCalories::from(vec![vec![300], vec![100], vec![200]]);
+
+        assert_eq!(
+            elves_calories
+                .most_snack_ready(3)
+                .unwrap()
+                .iter()
+                .map(|elf| elf.calories)
+                .collect::<Vec<TotalCalories>>(),
+            vec![300, 200, 100]
+        );
+        assert_eq!(
+            elves_calories.most_snack_ready(4),
+            Err(NotEnoughElves {
+                requested: 4,
+                available: 3
+            })
+        );
+    }
+}
@@ -1,165 +1,69 @@
-mod parser;
+use std::{error::Error, fs, fs::File, io::BufReader, path::PathBuf, process::ExitCode};
 
-use std::{fmt::Display, fs, ops::Deref};
+use clap::{Parser, ValueEnum};
 
-use parser::parse_elves_foods_calories;
+use day_1_calorie_counting::{
+    most_snack_ready_from_reader, parse_elves_calories, CaloriesReport, INPUT_FILE,
+};
 
-static INPUT_FILE: &str = "inputs/day1.txt";
+/// Count the calories carried by the elves.
+#[derive(Parser)]
+struct Args {
+    /// Path to the puzzle input.
+    #[arg(default_value = INPUT_FILE)]
+    input: PathBuf,
 
-/// A quantity of calories.
-type Calories = u32;
+    /// Print a statistics report of the calories carried by the elves instead of solving the puzzle.
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
 
-/// List of total calories carried by each elf sorted by descending snack-readiness.
-#[derive(Debug)]
-struct ElvesCalories(Vec<Calories>);
-
-impl Deref for ElvesCalories {
-    type Target = Vec<Calories>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+    /// Number of ranges the elves' total calories are grouped into for the report's histogram.
+    #[arg(long, default_value_t = 10)]
+    buckets: usize,
 }
 
-impl ElvesCalories {
-    /// Construct `ElvesCalories` from a list of calories by sorting by descending snack-readiness.
-    pub fn new(mut elves_calories: Vec<Calories>) -> ElvesCalories {
-        // Sort by descending calories
-        elves_calories.sort();
-        elves_calories.reverse();
-
-        ElvesCalories(elves_calories)
-    }
-
-    /// Returns the calories carried by each of some number of `elves` who are carrying the most calories
-    /// (AKA the most snack-ready).
-    pub fn most_snack_ready(&self, elves: usize) -> &[Calories] {
-        &self[..elves]
-    }
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Json,
 }
 
-impl Display for ElvesCalories {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (index, elf_calories) in self.iter().enumerate() {
-            writeln!(f, "{elf_calories} | {index}")?;
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    if let Some(format) = args.report {
+        let input = fs::read_to_string(&args.input)?;
+        let report = CaloriesReport::new(&parse_elves_calories(&input)?, args.buckets)?;
+        match format {
+            ReportFormat::Table => print!("{}", report),
+            ReportFormat::Json => println!("{}", report.to_json()),
         }
-        Ok(())
-    }
-}
-
-impl From<Vec<Vec<Calories>>> for ElvesCalories {
-    fn from(elves_foods_calories: Vec<Vec<Calories>>) -> Self {
-        // Sum up the calories of the foods each elf is carrying to get the total calories carried by each elf.
-        let elves_calories = elves_foods_calories
-            .iter()
-            .map(|elf_foods_calories| elf_foods_calories.iter().sum())
-            .collect();
-
-        ElvesCalories::new(elves_calories)
+        return Ok(());
     }
-}
-
-/// Returns [`ElvesCalories`] parsed from input data in `input_file`.
-fn elves_calories(input_file: &str) -> ElvesCalories {
-    let input = fs::read_to_string(input_file).expect("Failed to read input file");
-
-    // Parse the input into a list of what each elf recorded (a list of calories of foods they are carrying).
-    let elves_foods_calories: Vec<Vec<Calories>> = parse_elves_foods_calories(&input);
 
-    ElvesCalories::from(elves_foods_calories)
-}
-
-/// Returns the greatest calories carried by an elf calculated from the data in `input_file`.
-///
-/// This produces the solution to Day 1 Part 1.
-fn greatest_calories_carried(elves_calories: &ElvesCalories) -> Calories {
-    // Return the calories carried by the single most best snack-ready elf
-    elves_calories.most_snack_ready(1_usize)[0]
-}
-
-/// Returns the total sum of the calories carried by the most snack-ready `number_of_elves`.
-///
-/// The most snack-ready elves are the elves carrying the most total calories compared to other elves.
-///
-/// For example, passing `3` for `number_of_elves` returns the total sum of calories carried by the 3 elves
-/// who are carrying the most calories individually compared to the other elves.
-///
-/// This produces the solution to Day 1 Part 2.
-fn total_calories_of_most_snack_ready_elves(
-    elves_calories: &ElvesCalories,
-    elves: usize,
-) -> Calories {
-    elves_calories.most_snack_ready(elves).iter().sum()
-}
-
-fn main() {
-    let elves_calories = elves_calories(INPUT_FILE);
+    // Stream the input so even huge inputs are solved without reading them into memory.
+    let reader = BufReader::new(File::open(&args.input)?);
+    let most_snack_ready = most_snack_ready_from_reader(reader, 3)?;
 
     println!(
         "The snack-readiest elf is carrying {} calories.",
-        greatest_calories_carried(&elves_calories)
+        most_snack_ready[0]
     );
     println!(
         "The 3 most snack-ready elves are carrying a WHOPPING total of {} calories.",
-        total_calories_of_most_snack_ready_elves(&elves_calories, 3)
+        // Widen the sum so even the largest totals can't overflow.
+        most_snack_ready
+            .iter()
+            .map(|&calories| u128::from(calories))
+            .sum::<u128>()
     );
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use crate::{
-        elves_calories, greatest_calories_carried, total_calories_of_most_snack_ready_elves,
-        Calories, ElvesCalories, INPUT_FILE,
-    };
-
-    static EXAMPLE_INPUT_FILE: &str = "inputs/day1-example.txt";
-
-    #[test]
-    fn day_1_part_1_example() {
-        let elves_calories = elves_calories(EXAMPLE_INPUT_FILE);
-        assert_eq!(greatest_calories_carried(&elves_calories), 24000);
-    }
-
-    #[test]
-    fn solve_day_1_part_1() {
-        let elves_calories = elves_calories(INPUT_FILE);
-        assert_eq!(greatest_calories_carried(&elves_calories), 67658);
-    }
-
-    #[test]
-    fn day_1_part_2_example() {
-        let elves_calories = elves_calories(EXAMPLE_INPUT_FILE);
-        assert_eq!(
-            total_calories_of_most_snack_ready_elves(&elves_calories, 3),
-            45000
-        );
-    }
-
-    #[test]
-    fn solve_day_1_part_2() {
-        let elves_calories = elves_calories(INPUT_FILE);
-        assert_eq!(
-            total_calories_of_most_snack_ready_elves(&elves_calories, 3),
-            200158
-        );
-    }
-
-    #[test]
-    fn elves_calories_is_sorted() {
-        let unsorted_elves_calories: Vec<Calories> = vec![2, 1, 7, 3, 0, 5, 9, 4, 6, 8];
-
-        let elves_calories = ElvesCalories::new(unsorted_elves_calories);
-
-        assert_eq!(*elves_calories, vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
-    }
-
-    #[test]
-    fn elves_calories_from_elves_foods_calories() {
-        let elves_foods_calories: Vec<Vec<Calories>> =
-            vec![vec![100, 200], vec![7, 3], vec![10000]];
-
-        let elves_calories = ElvesCalories::from(elves_foods_calories);
-
-        assert_eq!(*elves_calories, vec![10000, 300, 10]);
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
     }
 }
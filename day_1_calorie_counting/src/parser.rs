@@ -1,16 +1,18 @@
 #![allow(clippy::result_large_err)]
 
-use pear::input::{Pear, Text};
+use pear::input::{Input as _, ParserInfo, Pear, Rewind, Text};
 use pear::macros::{parse, parse_error, parser};
-use pear::{combinators::*, parsers::*};
+use pear::parsers::*;
+
+use parse_error::ParseError;
 
 use crate::Calories;
 
 /// Block of calories lines of foods recorded by a given elf.
-type ElfFoodsCalories = Vec<Calories>;
+pub type ElfFoodsCalories = Vec<Calories>;
 
 /// Blocks of calories lines of foods recorded by all elves.
-type ElvesFoodsCalories = Vec<ElfFoodsCalories>;
+pub type ElvesFoodsCalories = Vec<ElfFoodsCalories>;
 
 /// Use `Text` as the `Input`; this parses `char` tokens.
 type Input<'a> = Pear<Text<'a>>;
@@ -25,67 +27,124 @@ type Result<'a, T> = pear::input::Result<T, Input<'a>>;
 /// Returns true if the char is a digit char (0, 1, 2, ..., 9)
 #[inline]
 fn is_num_char(&byte: &char) -> bool {
-    matches!(byte, '0'..='9')
+    byte.is_ascii_digit()
 }
 
-/// Returns true if the char is a newline char (\n)
+/// Returns true if the char is whitespace that may trail a line or fill a blank line (spaces, tabs, and the \r of a
+/// \r\n line ending)
 #[inline]
-fn is_newline_char(&byte: &char) -> bool {
-    byte == '\n'
+pub(crate) fn is_line_whitespace_char(&byte: &char) -> bool {
+    matches!(byte, ' ' | '\t' | '\r')
+}
+
+/// Parser that eats the end of a line: any trailing whitespace and a newline char (\n), unless the input has ended.
+#[parser]
+fn line_end<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    skip_while(is_line_whitespace_char)?;
+    if input.has(1) {
+        eat('\n')?;
+    }
 }
 
-/// Parser that simply eats a newline char (\n)
+/// Parser that eats a blank line separating the blocks of calories lines recorded by different elves.
 #[parser]
-fn newline<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+fn blank_line<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    skip_while(is_line_whitespace_char)?;
     eat('\n')?;
 }
 
-/// Parses a sequence of numeric chars into [`FoodCalories`].
+/// Parser that eats any blank lines, including a last one without a newline.
 ///
-/// This takes numeric chars until a newline is encountered, parses the chars as `FoodCalories`,
-/// and returns the parsed `FoodCalories` value.
+/// The whitespace starting a line that isn't blank is left in the input, so calories lines can't be indented.
+#[parser]
+fn blank_lines<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    loop {
+        let line_start = input.mark(&ParserInfo {
+            name: "blank_lines",
+            raw: true,
+        });
+        skip_while(is_line_whitespace_char)?;
+        if input.peek(|&c: &char| c == '\n') {
+            eat('\n')?;
+        } else {
+            if input.has(1) {
+                input.rewind_to(line_start);
+            }
+            break;
+        }
+    }
+}
+
+/// Parses a sequence of numeric chars into [`Calories`].
 #[parser]
 fn calories<'a>(input: &mut Input<'a>) -> Result<'a, Calories> {
-    take_some_while_until(is_num_char, '\n')?
-        .parse()
-        .or_else(|e| parse_error!("{}", e)?)
+    let digits = take_while(is_num_char)?;
+    if digits.is_empty() {
+        parse_error!("expected calories")?;
+    }
+    digits.parse().or_else(|e| parse_error!("{}", e)?)
 }
 
 /// Parses calories on a line.
 ///
-/// This parses `calories()`, eats a `newline()` char, and returns the parsed `FoodCalories` value.
+/// This parses `calories()`, eats the `line_end()`, and returns the parsed `Calories` value.
 #[parser]
 fn calories_line<'a>(input: &mut Input<'a>) -> Result<'a, Calories> {
-    (calories()?, newline()?).0
+    (calories()?, line_end()?).0
 }
 
-/// Parses a block of calories lines recorded by an elf into [`ElfCalories`].
+/// Parses a block of calories lines recorded by an elf into [`ElfFoodsCalories`].
 ///
-/// This parses a sequence of calories lines and eats a newline char if the next char matches.
+/// This parses calories lines for as long as the next line starts with a digit.
 #[parser]
 fn elf_calories<'a>(input: &mut Input<'a>) -> Result<'a, ElfFoodsCalories> {
-    let calories_lines: ElfFoodsCalories = try_collect(calories_line)?;
-    eat_if(is_newline_char)?;
+    let first_calories = calories_line()?;
+    let mut calories_lines: ElfFoodsCalories = vec![first_calories];
+    while input.peek(is_num_char) {
+        calories_lines.push(calories_line()?);
+    }
     calories_lines
 }
 
-/// Parses blocks of calories lines recorded by all of the elves into [`ElvesCalories`].
+/// Parses blocks of calories lines recorded by all of the elves into [`ElvesFoodsCalories`].
 ///
-/// This parses a sequence of blocks of calories lines.
+/// This parses a sequence of blocks of calories lines separated by blank lines, ignoring any blank lines at the
+/// start or end of the input.
 #[parser]
 fn elves_calories<'a>(input: &mut Input<'a>) -> Result<'a, ElvesFoodsCalories> {
-    let calories_blocks: ElvesFoodsCalories = try_collect(elf_calories)?;
+    let mut calories_blocks = ElvesFoodsCalories::new();
+    blank_lines()?;
+    while input.has(1) {
+        calories_blocks.push(elf_calories()?);
+        if input.has(1) {
+            blank_line()?;
+        }
+        blank_lines()?;
+    }
     calories_blocks
 }
 
 /// Parse `input` into `ElvesCalories` indicating the calories of the foods carried by each elf.
-pub(crate) fn parse_elves_foods_calories(input: &str) -> ElvesFoodsCalories {
-    parse!(elves_calories: Text::from(input)).expect("Failed to parse input")
+pub fn parse_elves_foods_calories(
+    input: &str,
+) -> std::result::Result<ElvesFoodsCalories, ParseError> {
+    parse!(elves_calories: Text::from(input)).map_err(|e| ParseError::from_pear(input, &e))
 }
 
+/// Inputs with the calories `[[100, 400], [900]]` whose lines end in different ways, named by how they do.
+#[cfg(test)]
+pub(crate) const LINE_ENDING_VARIANTS: [(&str, &str); 6] = [
+    ("no trailing newline", "100\n400\n\n900"),
+    ("single trailing newline", "100\n400\n\n900\n"),
+    ("trailing blank line", "100\n400\n\n900\n\n"),
+    ("CRLF line endings", "100\r\n400\r\n\r\n900\r\n"),
+    ("CRLF without trailing newline", "100\r\n400\r\n\r\n900"),
+    ("trailing whitespace", "100 \n400\t\n  \n900  \n \n\t"),
+];
+
 #[cfg(test)]
 mod test {
-    use crate::parser::parse_elves_foods_calories;
+    use crate::parser::{parse_elves_foods_calories, LINE_ENDING_VARIANTS};
 
     #[test]
     pub fn test_parse_elves_foods_calories() {
@@ -103,10 +162,35 @@ mod test {
 
 ";
 
-        let elves_foods_calories = parse_elves_foods_calories(INPUT);
+        let elves_foods_calories = parse_elves_foods_calories(INPUT).unwrap();
         assert_eq!(
             elves_foods_calories,
             vec![vec![100, 400], vec![900], vec![200, 200, 800], vec![1000]]
         );
     }
+
+    #[test]
+    pub fn test_parse_error_location() {
+        let error = parse_elves_foods_calories("100\n200\n\nabc\n\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.snippet, "abc");
+    }
+
+    #[test]
+    pub fn test_parse_line_ending_variants() {
+        for (variant, input) in LINE_ENDING_VARIANTS {
+            assert_eq!(
+                parse_elves_foods_calories(input).unwrap(),
+                vec![vec![100, 400], vec![900]],
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_parse_error_within_line() {
+        let error = parse_elves_foods_calories("100\n2x0\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
 }
@@ -0,0 +1,342 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{Calories, Elf, NotEnoughElves, TotalCalories};
+
+/// Percentiles of elves' total calories included in a [`CaloriesReport`].
+const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Width in chars of the longest bar when displaying the histogram.
+const HISTOGRAM_WIDTH: usize = 50;
+
+/// Statistics about the calories carried by the elves.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CaloriesReport {
+    /// Number of elves.
+    pub elves: usize,
+    /// Total calories carried by all elves.
+    pub total: u64,
+    /// Mean of the elves' total calories.
+    pub mean: f64,
+    /// Median of the elves' total calories.
+    pub median: f64,
+    /// Population standard deviation of the elves' total calories.
+    pub standard_deviation: f64,
+    /// Selected percentiles of the elves' total calories.
+    pub percentiles: Vec<Percentile>,
+    /// Number of elves whose total calories fall in each of a number of equally sized ranges.
+    pub histogram: Vec<Bucket>,
+    /// The single food item with the most calories, and the elf carrying it.
+    pub heaviest_food: Option<Food>,
+    /// Summary of the foods carried by each elf, in input order.
+    pub elf_summaries: Vec<ElfSummary>,
+}
+
+/// The total calories below which a `percentile` of the elves fall.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Percentile {
+    pub percentile: u8,
+    pub calories: f64,
+}
+
+/// Number of `elves` whose total calories are within `start..=end`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Bucket {
+    pub start: TotalCalories,
+    pub end: TotalCalories,
+    pub elves: usize,
+}
+
+/// A single food item and the position of the elf carrying it.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Food {
+    pub elf: usize,
+    pub calories: Calories,
+}
+
+/// The number of food items and total calories carried by the elf at `position` in the input.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ElfSummary {
+    pub position: usize,
+    pub items: usize,
+    pub calories: TotalCalories,
+}
+
+impl CaloriesReport {
+    /// Construct a `CaloriesReport` from the `elves` (in any order), with the elves' total calories grouped into
+    /// a histogram of `buckets` ranges.
+    ///
+    /// Returns an error if there are no elves to report on.
+    pub fn new(elves: &[Elf], buckets: usize) -> Result<CaloriesReport, NotEnoughElves> {
+        if elves.is_empty() {
+            return Err(NotEnoughElves {
+                requested: 1,
+                available: 0,
+            });
+        }
+
+        let mut elves_in_input_order: Vec<&Elf> = elves.iter().collect();
+        elves_in_input_order.sort_by_key(|elf| elf.position);
+
+        let elf_summaries: Vec<ElfSummary> = elves_in_input_order
+            .iter()
+            .map(|elf| ElfSummary {
+                position: elf.position,
+                items: elf.foods.len(),
+                calories: elf.calories,
+            })
+            .collect();
+
+        let mut sorted_calories: Vec<TotalCalories> =
+            elf_summaries.iter().map(|elf| elf.calories).collect();
+        sorted_calories.sort_unstable();
+
+        let elves = sorted_calories.len();
+        let total: u64 = sorted_calories.iter().sum();
+        let mean = total as f64 / elves as f64;
+        let variance = sorted_calories
+            .iter()
+            .map(|&c| (c as f64 - mean).powi(2))
+            .sum::<f64>()
+            / elves as f64;
+
+        let heaviest_food = elves_in_input_order
+            .iter()
+            .flat_map(|elf| {
+                elf.foods.iter().map(move |&calories| Food {
+                    elf: elf.position,
+                    calories,
+                })
+            })
+            // Prefer the first elf in input order when several carry equally heavy foods.
+            .fold(None, |heaviest: Option<Food>, food| match heaviest {
+                Some(heaviest) if heaviest.calories >= food.calories => Some(heaviest),
+                _ => Some(food),
+            });
+
+        Ok(CaloriesReport {
+            elves,
+            total,
+            mean,
+            median: percentile(&sorted_calories, 50),
+            standard_deviation: variance.sqrt(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| Percentile {
+                    percentile: p,
+                    calories: percentile(&sorted_calories, p),
+                })
+                .collect(),
+            histogram: histogram(&sorted_calories, buckets),
+            heaviest_food,
+            elf_summaries,
+        })
+    }
+
+    /// Returns the report serialized as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize calories report")
+    }
+}
+
+/// Returns the `p`th percentile of the non-empty `sorted_calories`, interpolating between the nearest ranks.
+fn percentile(sorted_calories: &[TotalCalories], p: u8) -> f64 {
+    let rank = f64::from(p) / 100.0 * (sorted_calories.len() - 1) as f64;
+    let lower = sorted_calories[rank.floor() as usize] as f64;
+    let upper = sorted_calories[rank.ceil() as usize] as f64;
+    lower + (upper - lower) * rank.fract()
+}
+
+/// Returns up to `buckets` equally sized ranges spanning the non-empty `sorted_calories` and the number of elves in
+/// each.
+///
+/// Fewer buckets are returned when there would otherwise be buckets past the greatest total calories.
+fn histogram(sorted_calories: &[TotalCalories], buckets: usize) -> Vec<Bucket> {
+    if buckets == 0 {
+        return Vec::new();
+    }
+    let min = sorted_calories[0];
+    let max = sorted_calories[sorted_calories.len() - 1];
+    // Widen the bounds so they can't overflow, even when the totals span every possible value.
+    let span = u128::from(max - min) + 1;
+    let width = span.div_ceil(buckets as u128);
+    let buckets = span.div_ceil(width);
+
+    let mut histogram: Vec<Bucket> = (0..buckets)
+        .map(|bucket| {
+            let start = u128::from(min) + bucket * width;
+            Bucket {
+                start: start as TotalCalories,
+                end: (start + width - 1).min(u128::from(max)) as TotalCalories,
+                elves: 0,
+            }
+        })
+        .collect();
+    for &calories in sorted_calories {
+        histogram[(u128::from(calories - min) / width) as usize].elves += 1;
+    }
+    histogram
+}
+
+impl Display for CaloriesReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Elves              | {}", self.elves)?;
+        writeln!(f, "Total calories     | {}", self.total)?;
+        writeln!(f, "Mean               | {:.2}", self.mean)?;
+        writeln!(f, "Median             | {:.2}", self.median)?;
+        writeln!(f, "Standard deviation | {:.2}", self.standard_deviation)?;
+        for p in &self.percentiles {
+            writeln!(f, "{:>2}th percentile    | {:.2}", p.percentile, p.calories)?;
+        }
+        if let Some(food) = &self.heaviest_food {
+            writeln!(
+                f,
+                "Heaviest food item | {} (carried by elf {})",
+                food.calories, food.elf
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Calories          | Elves")?;
+        let most_elves = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        for bucket in &self.histogram {
+            // Scale the bars so the fullest bucket's bar is `HISTOGRAM_WIDTH` long, rounding up so any
+            // non-empty bucket gets a visible bar.
+            let bar = (bucket.elves * HISTOGRAM_WIDTH + most_elves - 1) / most_elves.max(1);
+            writeln!(
+                f,
+                "{:>7} - {:>7} | {} {}",
+                bucket.start,
+                bucket.end,
+                "#".repeat(bar),
+                bucket.elves
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "  Elf | Items | Calories")?;
+        for elf in &self.elf_summaries {
+            writeln!(
+                f,
+                "{:>5} | {:>5} | {:>8}",
+                elf.position, elf.items, elf.calories
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        report::{histogram, Bucket, CaloriesReport, ElfSummary, Food},
+        ElvesCalories, NotEnoughElves, TotalCalories,
+    };
+
+    #[test]
+    fn report_statistics() {
+        let elves_calories = ElvesCalories::from(vec![
+            vec![100, 200],
+            vec![1000],
+            vec![50, 50, 200],
+            vec![600],
+        ]);
+
+        let report = CaloriesReport::new(&elves_calories, 2).unwrap();
+
+        assert_eq!(report.elves, 4);
+        assert_eq!(report.total, 2200);
+        assert_eq!(report.mean, 550.0);
+        assert_eq!(report.median, 450.0);
+        assert!((report.standard_deviation - 287.228).abs() < 0.001);
+        assert_eq!(report.percentiles[1].calories, 300.0);
+        assert_eq!(
+            report.histogram,
+            vec![
+                Bucket {
+                    start: 300,
+                    end: 650,
+                    elves: 3
+                },
+                Bucket {
+                    start: 651,
+                    end: 1000,
+                    elves: 1
+                }
+            ]
+        );
+        assert_eq!(
+            report.heaviest_food,
+            Some(Food {
+                elf: 1,
+                calories: 1000
+            })
+        );
+        assert_eq!(
+            report.elf_summaries[2],
+            ElfSummary {
+                position: 2,
+                items: 3,
+                calories: 300
+            }
+        );
+    }
+
+    #[test]
+    fn histogram_extremes() {
+        assert_eq!(
+            histogram(&[0, TotalCalories::MAX], 2),
+            vec![
+                Bucket {
+                    start: 0,
+                    end: TotalCalories::MAX / 2,
+                    elves: 1
+                },
+                Bucket {
+                    start: TotalCalories::MAX / 2 + 1,
+                    end: TotalCalories::MAX,
+                    elves: 1
+                }
+            ]
+        );
+
+        // Every bucket covers at least one total, so there are no buckets past the greatest.
+        assert_eq!(
+            histogram(&[5, 5, 5], 10),
+            vec![Bucket {
+                start: 5,
+                end: 5,
+                elves: 3
+            }]
+        );
+        assert_eq!(
+            histogram(&[0, 1, 2, 3, 4], 4)
+                .iter()
+                .map(|bucket| (bucket.start, bucket.end))
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (2, 3), (4, 4)]
+        );
+    }
+
+    #[test]
+    fn report_json() {
+        let report = CaloriesReport::new(&ElvesCalories::from(vec![vec![100, 200]]), 1).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(json["elves"], 1);
+        assert_eq!(json["heaviest_food"]["calories"], 200);
+        assert_eq!(json["elf_summaries"][0]["items"], 2);
+    }
+
+    #[test]
+    fn report_without_elves() {
+        assert_eq!(
+            CaloriesReport::new(&[], 10),
+            Err(NotEnoughElves {
+                requested: 1,
+                available: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+use parse_error::ParseError;
+
+use crate::{parser::is_line_whitespace_char, Calories, NotEnoughElves, TotalCalories};
+
+/// Calories carried by a bounded number of the most snack-ready elves seen so far.
+///
+/// Only the top `elves` totals are kept (in a min-heap), so memory use does not grow with the number of elves.
+#[derive(Debug)]
+pub struct TopElves {
+    elves: usize,
+    seen: usize,
+    top: BinaryHeap<Reverse<TotalCalories>>,
+}
+
+/// Error produced when reading elves' calories from a stream fails.
+#[derive(Debug)]
+pub enum StreamError {
+    /// Reading from the stream failed.
+    Io(io::Error),
+    /// A line of the stream is not valid calories.
+    Parse(ParseError),
+    /// The stream ended before enough elves were read.
+    NotEnoughElves(NotEnoughElves),
+    /// An elf's total calories are too large to count, at the `line` (numbered from 1) that overflowed them.
+    Overflow { line: usize },
+}
+
+impl TopElves {
+    /// Construct `TopElves` that keeps the calories of the most snack-ready `elves`.
+    pub fn new(elves: usize) -> TopElves {
+        TopElves {
+            elves,
+            seen: 0,
+            top: BinaryHeap::with_capacity(elves + 1),
+        }
+    }
+
+    /// Record the total `calories` carried by another elf.
+    pub fn push(&mut self, calories: TotalCalories) {
+        self.seen += 1;
+        self.top.push(Reverse(calories));
+        if self.top.len() > self.elves {
+            self.top.pop();
+        }
+    }
+
+    /// Returns the calories carried by the most snack-ready elves sorted by descending snack-readiness.
+    ///
+    /// Returns an error if fewer elves were pushed than this was constructed to keep.
+    pub fn into_sorted_vec(self) -> Result<Vec<TotalCalories>, NotEnoughElves> {
+        if self.seen < self.elves {
+            return Err(NotEnoughElves {
+                requested: self.elves,
+                available: self.seen,
+            });
+        }
+        // Sorting `Reverse` values ascending sorts the calories descending.
+        Ok(self
+            .top
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(calories)| calories)
+            .collect())
+    }
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "Failed to read input: {}", e),
+            StreamError::Parse(e) => write!(f, "Failed to parse input\n{}", e),
+            StreamError::NotEnoughElves(e) => write!(f, "{}", e),
+            StreamError::Overflow { line } => {
+                write!(f, "An elf's total calories overflow at line {}", line)
+            }
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<NotEnoughElves> for StreamError {
+    fn from(e: NotEnoughElves) -> Self {
+        StreamError::NotEnoughElves(e)
+    }
+}
+
+/// Returns the calories carried by each of the most snack-ready `elves`, read one line at a time from `reader`.
+///
+/// This accepts the same input as [`parse_elves_foods_calories`](crate::parse_elves_foods_calories) (blocks of
+/// calories lines separated by blank lines), but only holds a single line and the top `elves` totals in memory.
+pub fn most_snack_ready_from_reader<R: BufRead>(
+    mut reader: R,
+    elves: usize,
+) -> Result<Vec<TotalCalories>, StreamError> {
+    let mut top_elves = TopElves::new(elves);
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut offset = 0;
+    let mut elf_calories: Option<TotalCalories> = None;
+
+    loop {
+        line.clear();
+        let bytes_read = reader.read_line(&mut line)?;
+        if bytes_read == 0 {
+            break;
+        }
+        line_number += 1;
+
+        // Only the whitespace the parser allows at the end of a line is trimmed, so indented calories are rejected.
+        let calories = line
+            .strip_suffix('\n')
+            .unwrap_or(&line)
+            .trim_end_matches(|c| is_line_whitespace_char(&c));
+        if calories.is_empty() {
+            // A blank line ends the block of calories lines recorded by the current elf.
+            if let Some(total) = elf_calories.take() {
+                top_elves.push(total);
+            }
+        } else {
+            let food_calories = parse_calories(calories).map_err(|(column, expected)| {
+                StreamError::Parse(ParseError {
+                    offset: offset + column - 1,
+                    line: line_number,
+                    column,
+                    snippet: calories.to_string(),
+                    expected,
+                })
+            })?;
+            let total = elf_calories
+                .unwrap_or(0)
+                .checked_add(TotalCalories::from(food_calories))
+                .ok_or(StreamError::Overflow { line: line_number })?;
+            elf_calories = Some(total);
+        }
+
+        offset += bytes_read;
+    }
+    if let Some(total) = elf_calories {
+        top_elves.push(total);
+    }
+
+    Ok(top_elves.into_sorted_vec()?)
+}
+
+/// Parses a line's `calories`, or returns the column of the offending char and what was expected there.
+fn parse_calories(calories: &str) -> Result<Calories, (usize, String)> {
+    if let Some((index, c)) = calories.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        let column = calories[..index].chars().count() + 1;
+        return Err((column, format!("expected calories but found {:?}", c)));
+    }
+    calories.parse().map_err(|e| (1, format!("{}", e)))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::{
+        most_snack_ready_from_reader, parse_elves_calories, parser::LINE_ENDING_VARIANTS,
+        NotEnoughElves, StreamError, TopElves,
+    };
+
+    #[test]
+    fn top_elves_keeps_most_snack_ready() {
+        let mut top_elves = TopElves::new(3);
+        for calories in [2, 1, 7, 3, 0, 5, 9, 4, 6, 8] {
+            top_elves.push(calories);
+        }
+
+        assert_eq!(top_elves.into_sorted_vec(), Ok(vec![9, 8, 7]));
+    }
+
+    #[test]
+    fn top_elves_not_enough_elves() {
+        let mut top_elves = TopElves::new(3);
+        top_elves.push(100);
+
+        assert_eq!(
+            top_elves.into_sorted_vec(),
+            Err(NotEnoughElves {
+                requested: 3,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn streaming_matches_parser() {
+        let input = std::fs::read_to_string("inputs/day1-example.txt").unwrap();
+        let elves_calories = parse_elves_calories(&input).unwrap();
+
+        let top = most_snack_ready_from_reader(Cursor::new(&input), 3).unwrap();
+
+        assert_eq!(top, elves_calories.calories()[..3]);
+    }
+
+    #[test]
+    fn streaming_accepts_parser_inputs() {
+        let whitespace_variants = [
+            ("leading blank lines", " \n\t\r\n100\n400\n\n900\n"),
+            ("indented first line", "  100\n400\n\n900\n"),
+            ("indented block", "100\n400\n\n 900\n"),
+            ("indented line within block", "100\n 400\n\n900\n"),
+            ("non-breaking space", "100\n400\n\u{a0}\n900\n"),
+            ("lone carriage return", "100\r400\n\n900\n"),
+        ];
+
+        for &(variant, input) in LINE_ENDING_VARIANTS.iter().chain(&whitespace_variants) {
+            let parsed =
+                parse_elves_calories(input).map(|elves_calories| elves_calories.calories());
+            let streamed = most_snack_ready_from_reader(Cursor::new(input), 1);
+            match (parsed, streamed) {
+                (Ok(parsed), Ok(streamed)) => assert_eq!(streamed, parsed[..1], "{}", variant),
+                (Err(parsed), Err(StreamError::Parse(streamed))) => {
+                    assert_eq!(streamed.line, parsed.line, "{}", variant)
+                }
+                (parsed, streamed) => panic!(
+                    "{}: parsed {:?} but streamed {:?}",
+                    variant, parsed, streamed
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_totals_beyond_food_calories() {
+        let input = "4294967295\n4294967295\n\n1\n";
+
+        let top = most_snack_ready_from_reader(Cursor::new(input), 2).unwrap();
+
+        assert_eq!(top, vec![8589934590, 1]);
+    }
+
+    #[test]
+    fn streaming_parse_error_location() {
+        let input = "100\r\n200\r\n\r\n3x0\r\n";
+
+        match most_snack_ready_from_reader(Cursor::new(input), 1) {
+            Err(StreamError::Parse(e)) => {
+                assert_eq!((e.line, e.column, e.offset), (4, 2, 13));
+                assert_eq!(e.snippet, "3x0");
+            }
+            result => panic!("Expected a parse error, got {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use parse_error::ParseError;
+
+use crate::{interpretation::keep_furthest, total_score, Interpretation, Move, Outcome, Score};
+
+/// Letters encoding the three values of a column of the strategy guide, in a fixed order of values.
+pub type Letters = [u8; 3];
+
+/// The letters used to encode each column of a strategy guide.
+///
+/// A cipher is written as `key=LETTERS` entries separated by commas or newlines, where `LETTERS` gives the
+/// letters encoding each value in order. For example, the cipher used in the puzzle is:
+///
+/// ```text
+/// # Opponent's Rock, Paper and Scissors
+/// opponent=ABC
+/// # Player's Rock, Paper and Scissors
+/// player=XYZ
+/// # Desired Loss, Draw and Win
+/// outcome=XYZ
+/// ```
+///
+/// Any key that is left out keeps the letters used in the puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cipher {
+    /// Letters encoding the opponent's moves, in the order of [`Move::ALL`].
+    pub opponent_moves: Letters,
+    /// Letters encoding the player's moves, in the order of [`Move::ALL`].
+    pub player_moves: Letters,
+    /// Letters encoding the desired outcomes, in the order of [`Outcome::ALL`].
+    pub outcomes: Letters,
+}
+
+/// Error produced when a cipher can't be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CipherError {
+    /// An entry isn't written as `key=LETTERS`.
+    Malformed(String),
+    /// An entry's key isn't `opponent`, `player` or `outcome`.
+    UnknownKey(String),
+    /// An entry doesn't give 3 distinct letters.
+    InvalidLetters { key: String, letters: String },
+}
+
+/// Total scores of a strategy guide read with a [`Cipher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CipherScores {
+    pub cipher: Cipher,
+    /// Total score when the second column is the player's move, or `None` if it isn't encoded as moves.
+    pub moves_score: Option<Score>,
+    /// Total score when the second column is the desired outcome, or `None` if it isn't encoded as outcomes.
+    pub outcomes_score: Option<Score>,
+}
+
+impl Default for Cipher {
+    /// The cipher used in the puzzle.
+    fn default() -> Self {
+        Cipher {
+            opponent_moves: *b"ABC",
+            player_moves: *b"XYZ",
+            outcomes: *b"XYZ",
+        }
+    }
+}
+
+impl Cipher {
+    /// Returns the opponent's move encoded by `byte`, if there is one.
+    pub fn decode_opponent_move(&self, byte: u8) -> Option<Move> {
+        decode(&self.opponent_moves, byte).map(|index| Move::ALL[index])
+    }
+
+    /// Returns the player's move encoded by `byte`, if there is one.
+    pub fn decode_player_move(&self, byte: u8) -> Option<Move> {
+        decode(&self.player_moves, byte).map(|index| Move::ALL[index])
+    }
+
+    /// Returns the desired outcome encoded by `byte`, if there is one.
+    pub fn decode_outcome(&self, byte: u8) -> Option<Outcome> {
+        decode(&self.outcomes, byte).map(|index| Outcome::ALL[index])
+    }
+
+    /// Returns this cipher with the letters of the second column (the player's moves and desired outcomes)
+    /// rearranged in each of the 6 possible orders.
+    ///
+    /// The player's moves and the desired outcomes each keep their own letters, and are rearranged in the same way:
+    /// the first cipher is this one, and the last reverses both.
+    pub fn second_column_permutations(&self) -> Vec<Cipher> {
+        permutations(self.player_moves)
+            .into_iter()
+            .zip(permutations(self.outcomes))
+            .map(|(player_moves, outcomes)| Cipher {
+                player_moves,
+                outcomes,
+                ..*self
+            })
+            .collect()
+    }
+}
+
+/// Returns the index of `byte` in `letters`, if it is one of them.
+pub(crate) fn decode(letters: &Letters, byte: u8) -> Option<usize> {
+    letters.iter().position(|&letter| letter == byte)
+}
+
+/// Returns all 6 orders of `letters`.
+fn permutations([a, b, c]: Letters) -> Vec<Letters> {
+    vec![
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+/// Formats `letters` as a list for error messages, e.g. "X, Y or Z".
+pub(crate) fn describe(letters: &Letters) -> String {
+    format!(
+        "{}, {} or {}",
+        letters[0] as char, letters[1] as char, letters[2] as char
+    )
+}
+
+impl FromStr for Cipher {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cipher = Cipher::default();
+
+        let entries = s
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty());
+        for entry in entries {
+            let (key, letters) = entry
+                .split_once('=')
+                .ok_or_else(|| CipherError::Malformed(entry.to_string()))?;
+            let (key, letters) = (key.trim(), letters.trim());
+
+            let column = match key {
+                "opponent" => &mut cipher.opponent_moves,
+                "player" => &mut cipher.player_moves,
+                "outcome" => &mut cipher.outcomes,
+                _ => return Err(CipherError::UnknownKey(key.to_string())),
+            };
+            *column = parse_letters(letters).ok_or_else(|| CipherError::InvalidLetters {
+                key: key.to_string(),
+                letters: letters.to_string(),
+            })?;
+        }
+
+        Ok(cipher)
+    }
+}
+
+/// Parses 3 distinct printable ASCII letters.
+fn parse_letters(letters: &str) -> Option<Letters> {
+    let letters: Letters = letters.as_bytes().try_into().ok()?;
+    let distinct = letters[0] != letters[1] && letters[1] != letters[2] && letters[0] != letters[2];
+    (distinct && letters.iter().all(u8::is_ascii_graphic)).then_some(letters)
+}
+
+impl Display for Cipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = |letters: &Letters| String::from_utf8_lossy(letters).into_owned();
+        write!(
+            f,
+            "opponent={},player={},outcome={}",
+            text(&self.opponent_moves),
+            text(&self.player_moves),
+            text(&self.outcomes)
+        )
+    }
+}
+
+impl Display for CipherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CipherError::Malformed(entry) => {
+                write!(f, "Cipher entry {entry:?} is not written as key=LETTERS")
+            }
+            CipherError::UnknownKey(key) => write!(
+                f,
+                "Cipher key {key:?} is not one of opponent, player or outcome"
+            ),
+            CipherError::InvalidLetters { key, letters } => write!(
+                f,
+                "Cipher entry for {key} must give 3 distinct letters, but {letters:?} was given"
+            ),
+        }
+    }
+}
+
+impl Error for CipherError {}
+
+/// Returns the total scores of the strategy guide in `input` under every order of the letters of its second
+/// column (see [`Cipher::second_column_permutations`]).
+///
+/// An interpretation whose letters the guide isn't encoded with has no scores. If the guide can't be read under
+/// either interpretation, returns the error of whichever reading got furthest into the guide.
+pub fn score_second_column_permutations(
+    input: &str,
+    cipher: &Cipher,
+) -> Result<Vec<CipherScores>, ParseError> {
+    let mut error = None;
+    let mut score = |interpretation: Interpretation, cipher: &Cipher| match interpretation
+        .rounds(input, cipher)
+    {
+        Ok(rounds) => Some(total_score(&rounds)),
+        Err(e) => {
+            keep_furthest(&mut error, e);
+            None
+        }
+    };
+    let scores: Vec<CipherScores> = cipher
+        .second_column_permutations()
+        .into_iter()
+        .map(|cipher| CipherScores {
+            cipher,
+            moves_score: score(Interpretation::Moves, &cipher),
+            outcomes_score: score(Interpretation::Outcomes, &cipher),
+        })
+        .collect();
+
+    let unread = scores
+        .iter()
+        .all(|scores| scores.moves_score.is_none() && scores.outcomes_score.is_none());
+    match error {
+        Some(e) if unread => Err(e),
+        _ => Ok(scores),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cipher::{score_second_column_permutations, Cipher, CipherError},
+        parse_moves_strategy_guide_with_cipher, total_score, Interpretation, Move, Outcome,
+    };
+
+    static EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn default_cipher_decodes_puzzle_letters() {
+        let cipher = Cipher::default();
+
+        assert_eq!(cipher.decode_opponent_move(b'C'), Some(Move::Scissors));
+        assert_eq!(cipher.decode_player_move(b'Y'), Some(Move::Paper));
+        assert_eq!(cipher.decode_outcome(b'X'), Some(Outcome::Loss));
+        assert_eq!(cipher.decode_player_move(b'A'), None);
+    }
+
+    #[test]
+    fn parse_cipher() {
+        let cipher: Cipher = "# Swapped\nplayer = ZYX, outcome=ZXY\n".parse().unwrap();
+
+        assert_eq!(
+            cipher,
+            Cipher {
+                opponent_moves: *b"ABC",
+                player_moves: *b"ZYX",
+                outcomes: *b"ZXY",
+            }
+        );
+        assert_eq!(cipher.to_string().parse::<Cipher>().unwrap(), cipher);
+    }
+
+    #[test]
+    fn invalid_ciphers() {
+        assert_eq!(
+            "player".parse::<Cipher>(),
+            Err(CipherError::Malformed("player".to_string()))
+        );
+        assert_eq!(
+            "elf=ABC".parse::<Cipher>(),
+            Err(CipherError::UnknownKey("elf".to_string()))
+        );
+        assert_eq!(
+            "outcome=XXZ".parse::<Cipher>(),
+            Err(CipherError::InvalidLetters {
+                key: "outcome".to_string(),
+                letters: "XXZ".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn custom_cipher_moves() {
+        let cipher: Cipher = "opponent=RPS,player=rps".parse().unwrap();
+
+        let rounds = parse_moves_strategy_guide_with_cipher("R p\nP r\nS s\n", &cipher).unwrap();
+        assert_eq!(total_score(&rounds), 15);
+
+        let error = parse_moves_strategy_guide_with_cipher("R p\nA X\n", &cipher).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "expected an encoded move (R, P or S)");
+    }
+
+    #[test]
+    fn second_column_permutations() {
+        let scores = score_second_column_permutations(EXAMPLE_INPUT, &Cipher::default()).unwrap();
+
+        assert_eq!(scores.len(), 6);
+        assert_eq!(
+            (scores[0].moves_score, scores[0].outcomes_score),
+            (Some(15), Some(12))
+        );
+        // Reading X, Y and Z as Scissors, Paper and Rock (or Win, Draw and Loss) instead.
+        let reversed = scores
+            .iter()
+            .find(|scores| scores.cipher.player_moves == *b"ZYX")
+            .unwrap();
+        assert_eq!(
+            (reversed.moves_score, reversed.outcomes_score),
+            (Some(24), Some(15))
+        );
+    }
+
+    #[test]
+    fn permutations_of_one_interpretations_letters() {
+        let cipher: Cipher = "outcome=LDW".parse().unwrap();
+
+        let scores = score_second_column_permutations("A W\nB L\nC D\n", &cipher).unwrap();
+        assert!(scores.iter().all(|scores| scores.moves_score.is_none()));
+        assert_eq!(scores[0].outcomes_score, Some(15));
+
+        // Reading the guide as outcomes gets as far as the Q, while reading it as moves stops at the first W.
+        let error = score_second_column_permutations("A W\nB Q\n", &cipher).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn permutations_keep_each_columns_letters() {
+        let cipher: Cipher = "outcome=LDW".parse().unwrap();
+        let permutations = cipher.second_column_permutations();
+
+        assert_eq!(permutations[0], cipher);
+        assert_eq!(permutations[5].player_moves, *b"ZYX");
+        assert_eq!(permutations[5].outcomes, *b"WDL");
+        for permuted in &permutations {
+            let mut outcomes = permuted.outcomes;
+            outcomes.sort_unstable();
+            assert_eq!(outcomes, *b"DLW");
+        }
+
+        // Outcomes keep their own order of the letters shared with the player's moves.
+        let cipher: Cipher = "outcome=ZXY".parse().unwrap();
+        let scores = score_second_column_permutations(EXAMPLE_INPUT, &cipher).unwrap();
+        assert_eq!(scores[0].cipher, cipher);
+        assert_eq!(
+            scores[0].outcomes_score,
+            Some(total_score(
+                &Interpretation::Outcomes
+                    .rounds(EXAMPLE_INPUT, &cipher)
+                    .unwrap()
+            ))
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Move, Round};
+
+/// Probability of playing each move, in the order of [`Move::ALL`].
+pub type Distribution = [f64; 3];
+
+/// Playing every move equally often.
+pub const UNIFORM: Distribution = [1.0 / 3.0; 3];
+
+/// Tolerance for floating point comparisons while solving linear programs.
+const EPSILON: f64 = 1e-9;
+
+/// The mixed strategy that is a Nash equilibrium of Rock Paper Scissors played for points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equilibrium {
+    /// Probability of the player choosing each move.
+    pub strategy: Distribution,
+    /// Expected points the player scores per round beyond the opponent when both play the equilibrium strategy.
+    pub value: f64,
+}
+
+/// Expected scores per round against an opponent playing moves with a given distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExpectedScores {
+    /// Expected score when playing the [`Equilibrium`] strategy.
+    pub equilibrium: f64,
+    /// Expected score when playing every move equally often.
+    pub uniform: f64,
+    /// The single move scoring the most against the opponent, and its expected score.
+    pub best_response: (Move, f64),
+}
+
+/// Returns the player's score for each of the player's moves (rows) against each of the opponent's moves
+/// (columns), in the order of [`Move::ALL`].
+pub fn score_matrix() -> [[f64; 3]; 3] {
+    Move::ALL.map(|player| Move::ALL.map(|opponent| f64::from(Round { player, opponent }.score())))
+}
+
+/// Returns how many more points the player scores than the opponent for each of the player's moves (rows) against
+/// each of the opponent's moves (columns), in the order of [`Move::ALL`].
+///
+/// Both players score their rounds the same way, so this is the payoff matrix of the zero-sum game of trying to
+/// outscore the opponent.
+pub fn payoff_matrix() -> [[f64; 3]; 3] {
+    let scores = score_matrix();
+    [0, 1, 2]
+        .map(|player| [0, 1, 2].map(|opponent| scores[player][opponent] - scores[opponent][player]))
+}
+
+/// Returns the Nash equilibrium mixed strategy for trying to outscore the opponent (see [`payoff_matrix`]).
+pub fn equilibrium() -> Equilibrium {
+    let payoffs = payoff_matrix().map(Vec::from).to_vec();
+    let (strategy, value) = solve_zero_sum(&payoffs).expect("the payoff matrix is a whole game");
+
+    Equilibrium {
+        strategy: [strategy[0], strategy[1], strategy[2]],
+        value,
+    }
+}
+
+/// Returns the optimal mixed strategy of the row player and the value of the zero-sum game with the `payoffs` to
+/// the row player.
+///
+/// The game is solved as the linear program of the column player, which after shifting the payoffs to be positive
+/// is to maximize `sum(y)` subject to `payoffs * y <= 1` and `y >= 0`. The row player's strategy is read from the
+/// dual solution of the final simplex tableau.
+///
+/// Returns `None` if there are no rows, the rows are empty or of different lengths, or any payoff is NaN or
+/// infinite.
+pub fn solve_zero_sum(payoffs: &[Vec<f64>]) -> Option<(Vec<f64>, f64)> {
+    let rows = payoffs.len();
+    let columns = payoffs.first()?.len();
+    if columns == 0
+        || payoffs.iter().any(|row| row.len() != columns)
+        || payoffs.iter().flatten().any(|payoff| !payoff.is_finite())
+    {
+        return None;
+    }
+    let lowest = payoffs
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - lowest;
+
+    // Tableau of the constraints (with a slack variable for each row) followed by the objective row, where the
+    // last column holds the right hand sides.
+    let width = columns + rows + 1;
+    let mut tableau: Vec<Vec<f64>> = payoffs
+        .iter()
+        .enumerate()
+        .map(|(row, payoffs)| {
+            let mut constraint = vec![0.0; width];
+            for (column, payoff) in payoffs.iter().enumerate() {
+                constraint[column] = payoff + shift;
+            }
+            constraint[columns + row] = 1.0;
+            constraint[width - 1] = 1.0;
+            constraint
+        })
+        .collect();
+    let mut objective = vec![0.0; width];
+    objective[..columns].fill(-1.0);
+    tableau.push(objective);
+    let mut basis: Vec<usize> = (columns..columns + rows).collect();
+
+    // Pivot until no variable improves the objective, choosing by Bland's rule to avoid cycling.
+    while let Some(entering) = (0..width - 1).find(|&column| tableau[rows][column] < -EPSILON) {
+        let leaving = (0..rows)
+            .filter(|&row| tableau[row][entering] > EPSILON)
+            .min_by(|&a, &b| {
+                let ratio = |row: usize| tableau[row][width - 1] / tableau[row][entering];
+                ratio(a).total_cmp(&ratio(b)).then(basis[a].cmp(&basis[b]))
+            })
+            .expect("the column player's program is bounded");
+
+        let pivot = tableau[leaving][entering];
+        tableau[leaving]
+            .iter_mut()
+            .for_each(|value| *value /= pivot);
+        let pivot_row = tableau[leaving].clone();
+        for (row, values) in tableau.iter_mut().enumerate() {
+            let factor = values[entering];
+            if row != leaving && factor != 0.0 {
+                for (value, pivot_value) in values.iter_mut().zip(&pivot_row) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        basis[leaving] = entering;
+    }
+
+    let total = tableau[rows][width - 1];
+    let strategy = (0..rows)
+        .map(|row| tableau[rows][columns + row] / total)
+        .collect();
+    Some((strategy, 1.0 / total - shift))
+}
+
+/// Returns the player's expected score per round when the `player` and `opponent` choose moves with the given
+/// distributions.
+pub fn expected_score(player: &Distribution, opponent: &Distribution) -> f64 {
+    let scores = score_matrix();
+    (0..3)
+        .flat_map(|p| (0..3).map(move |o| (p, o)))
+        .map(|(p, o)| player[p] * opponent[o] * scores[p][o])
+        .sum()
+}
+
+/// Returns the expected scores per round of the equilibrium, uniform and best single move strategies against an
+/// `opponent` choosing moves with the given distribution.
+pub fn expected_scores(opponent: &Distribution) -> ExpectedScores {
+    let best_response = Move::ALL
+        .iter()
+        .map(|&player| {
+            let mut pure = [0.0; 3];
+            pure[player.index()] = 1.0;
+            (player, expected_score(&pure, opponent))
+        })
+        .fold(None, |best: Option<(Move, f64)>, response| match best {
+            Some(best) if best.1 >= response.1 => Some(best),
+            _ => Some(response),
+        })
+        .expect("there are moves to choose from");
+
+    ExpectedScores {
+        equilibrium: expected_score(&equilibrium().strategy, opponent),
+        uniform: expected_score(&UNIFORM, opponent),
+        best_response,
+    }
+}
+
+/// Returns the distribution of the opponent's moves in the `rounds` of a strategy guide.
+///
+/// Returns `None` if there are no rounds.
+pub fn opponent_distribution(rounds: &[Round]) -> Option<Distribution> {
+    let mut counts = [0.0; 3];
+    for round in rounds {
+        counts[round.opponent.index()] += 1.0;
+    }
+    normalize(counts)
+}
+
+/// Returns the distribution with probabilities proportional to the non-negative `weights`.
+///
+/// Returns `None` if any weight is negative, NaN or infinite, if they are all zero, or if their total is infinite.
+pub fn normalize(weights: [f64; 3]) -> Option<Distribution> {
+    let total: f64 = weights.iter().sum();
+    if weights
+        .iter()
+        .any(|&weight| !weight.is_finite() || weight < 0.0)
+        || !total.is_finite()
+        || total <= 0.0
+    {
+        return None;
+    }
+    Some(weights.map(|weight| weight / total))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        equilibrium::{
+            equilibrium, expected_score, expected_scores, normalize, opponent_distribution,
+            payoff_matrix, solve_zero_sum, UNIFORM,
+        },
+        rounds_from_moves_strategy_guide, Move, INPUT_FILE,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{actual} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn solve_matching_pennies() {
+        let (strategy, value) = solve_zero_sum(&[vec![1.0, -1.0], vec![-1.0, 1.0]]).unwrap();
+
+        assert_close(strategy[0], 0.5);
+        assert_close(strategy[1], 0.5);
+        assert_close(value, 0.0);
+    }
+
+    #[test]
+    fn solve_dominated_game() {
+        // The second row is always better, so it should always be played.
+        let (strategy, value) = solve_zero_sum(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+
+        assert_close(strategy[0], 0.0);
+        assert_close(strategy[1], 1.0);
+        assert_close(value, 3.0);
+    }
+
+    #[test]
+    fn unsolvable_games() {
+        assert_eq!(solve_zero_sum(&[]), None);
+        assert_eq!(solve_zero_sum(&[vec![], vec![]]), None);
+        assert_eq!(solve_zero_sum(&[vec![1.0, 2.0], vec![3.0]]), None);
+        assert_eq!(solve_zero_sum(&[vec![1.0, f64::NAN]]), None);
+    }
+
+    #[test]
+    fn equilibrium_is_unexploitable() {
+        let equilibrium = equilibrium();
+        let payoffs = payoff_matrix();
+
+        assert_close(equilibrium.strategy.iter().sum(), 1.0);
+        assert!(equilibrium.strategy.iter().all(|&p| p >= 0.0));
+        // The game is symmetric, so neither player can expect to outscore the other.
+        assert_close(equilibrium.value, 0.0);
+        for opponent in Move::ALL {
+            let payoff: f64 = (0..3)
+                .map(|player| equilibrium.strategy[player] * payoffs[player][opponent.index()])
+                .sum();
+            assert!(payoff >= -1e-9, "{opponent:?} exploits the equilibrium");
+        }
+        // Uniform play is exploitable because the moves score differently.
+        assert!(equilibrium.strategy != UNIFORM);
+    }
+
+    #[test]
+    fn expected_scores_against_uniform() {
+        // Each move scores its own points plus 3 points on average against a uniform opponent.
+        assert_close(expected_score(&UNIFORM, &UNIFORM), 2.0 + 3.0);
+
+        let scores = expected_scores(&UNIFORM);
+        assert_eq!(scores.best_response.0, Move::Scissors);
+        assert_close(scores.best_response.1, 3.0 + 3.0);
+    }
+
+    #[test]
+    fn guide_derived_distribution() {
+        let rounds = rounds_from_moves_strategy_guide(INPUT_FILE);
+        let distribution = opponent_distribution(&rounds).unwrap();
+
+        assert_close(distribution.iter().sum(), 1.0);
+        assert_eq!(opponent_distribution(&[]), None);
+        assert_eq!(normalize([1.0, 1.0, 2.0]), Some([0.25, 0.25, 0.5]));
+        assert_eq!(normalize([0.0, 0.0, 0.0]), None);
+        assert_eq!(normalize([f64::NAN, 1.0, 1.0]), None);
+        assert_eq!(normalize([f64::INFINITY, 1.0, 1.0]), None);
+        assert_eq!(normalize([f64::MAX, f64::MAX, 1.0]), None);
+    }
+}
@@ -0,0 +1,512 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{Move, Outcome, Score};
+
+/// Index of a move in a [`Game`]'s list of moves.
+pub type MoveIndex = usize;
+
+/// A move that can be played in a [`Game`] and the points scored for playing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveRule {
+    pub name: String,
+    pub score: Score,
+}
+
+/// The points scored for each [`Outcome`] of a round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutcomeScores {
+    pub loss: Score,
+    pub draw: Score,
+    pub win: Score,
+}
+
+/// The moves played by both players in a round of a [`Game`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameRound {
+    pub player: MoveIndex,
+    pub opponent: MoveIndex,
+}
+
+/// A generalized game of Rock Paper Scissors played with any odd number of moves.
+///
+/// The game is a balanced cyclic tournament: every pair of distinct moves has exactly one winner, and every move
+/// beats exactly half of the other moves (and loses to the other half).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<MoveRule>,
+    /// `beats[a][b]` is true when move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+    outcome_scores: OutcomeScores,
+}
+
+/// Error produced when the moves and rules given for a [`Game`] don't form a balanced cyclic tournament.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameError {
+    /// Games need an odd number of at least 3 moves for every move to beat exactly half of the others.
+    MoveCount(usize),
+    /// Two moves have the same name.
+    DuplicateMove(String),
+    /// A rule refers to a move that isn't in the game.
+    UnknownMove(String),
+    /// A rule says a move beats itself.
+    BeatsItself(String),
+    /// Neither or both of the moves beat each other.
+    Undecided(String, String),
+    /// A move doesn't beat exactly half of the other moves.
+    Unbalanced(String),
+    /// A move's score added to an outcome's score is too large to be a [`Score`].
+    ScoreOverflow(String),
+}
+
+impl MoveRule {
+    /// Construct a `MoveRule` for the move called `name` which scores `score` points.
+    pub fn new(name: &str, score: Score) -> MoveRule {
+        MoveRule {
+            name: name.to_string(),
+            score,
+        }
+    }
+}
+
+impl OutcomeScores {
+    /// Returns the points scored for an `outcome`.
+    pub fn score(&self, outcome: Outcome) -> Score {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+impl Default for OutcomeScores {
+    /// The outcome scores used in the puzzle.
+    fn default() -> Self {
+        OutcomeScores {
+            loss: Outcome::Loss.score(),
+            draw: Outcome::Draw.score(),
+            win: Outcome::Win.score(),
+        }
+    }
+}
+
+impl Game {
+    /// Construct a `Game` from its `moves`, a list of `(winner, loser)` rules naming which move beats which, and
+    /// the points scored for each outcome.
+    pub fn new(
+        moves: Vec<MoveRule>,
+        rules: &[(&str, &str)],
+        outcome_scores: OutcomeScores,
+    ) -> Result<Game, GameError> {
+        let mut beats = vec![vec![false; moves.len()]; moves.len()];
+        let index = |name: &str| {
+            moves
+                .iter()
+                .position(|rule| rule.name == name)
+                .ok_or_else(|| GameError::UnknownMove(name.to_string()))
+        };
+        for &(winner, loser) in rules {
+            beats[index(winner)?][index(loser)?] = true;
+        }
+
+        Game {
+            moves,
+            beats,
+            outcome_scores,
+        }
+        .validated()
+    }
+
+    /// Construct a `Game` where each move beats the half of the other moves that come before it in `moves`,
+    /// wrapping around from the start to the end of the list.
+    ///
+    /// For example, with `[Rock, Paper, Scissors]` Paper beats Rock, Scissors beats Paper, and Rock beats Scissors.
+    pub fn cyclic(moves: Vec<MoveRule>, outcome_scores: OutcomeScores) -> Result<Game, GameError> {
+        let n = moves.len();
+        let beats = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| (1..=n / 2).contains(&((a + n - b) % n)))
+                    .collect()
+            })
+            .collect();
+
+        Game {
+            moves,
+            beats,
+            outcome_scores,
+        }
+        .validated()
+    }
+
+    /// The classic game of Rock Paper Scissors played in the puzzle.
+    ///
+    /// The moves are indexed in the order of [`Move::ALL`] (see [`Move::index`]).
+    pub fn rock_paper_scissors() -> Game {
+        let moves = Move::ALL
+            .iter()
+            .map(|m| MoveRule::new(&m.to_string(), m.score()))
+            .collect();
+        let beats = Move::ALL
+            .iter()
+            .map(|&a| Move::ALL.iter().map(|&b| a.beats() == b).collect())
+            .collect();
+
+        Game {
+            moves,
+            beats,
+            outcome_scores: OutcomeScores::default(),
+        }
+        .validated()
+        .expect("Rock Paper Scissors is a valid game")
+    }
+
+    /// Rock Paper Scissors Lizard Spock, scoring Lizard 4 points and Spock 5 points.
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        let moves = vec![
+            MoveRule::new("Rock", 1),
+            MoveRule::new("Paper", 2),
+            MoveRule::new("Scissors", 3),
+            MoveRule::new("Lizard", 4),
+            MoveRule::new("Spock", 5),
+        ];
+        let rules = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+
+        Game::new(moves, &rules, OutcomeScores::default())
+            .expect("Rock Paper Scissors Lizard Spock is a valid game")
+    }
+
+    /// Returns the game if its moves and rules form a balanced cyclic tournament.
+    fn validated(self) -> Result<Game, GameError> {
+        let n = self.moves.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(GameError::MoveCount(n));
+        }
+        for (a, rule) in self.moves.iter().enumerate() {
+            if self.moves[..a].iter().any(|other| other.name == rule.name) {
+                return Err(GameError::DuplicateMove(rule.name.clone()));
+            }
+            if self.beats[a][a] {
+                return Err(GameError::BeatsItself(rule.name.clone()));
+            }
+            for b in a + 1..n {
+                if self.beats[a][b] == self.beats[b][a] {
+                    return Err(GameError::Undecided(
+                        rule.name.clone(),
+                        self.moves[b].name.clone(),
+                    ));
+                }
+            }
+        }
+        for (a, rule) in self.moves.iter().enumerate() {
+            if self.beats[a].iter().filter(|&&beats| beats).count() != n / 2 {
+                return Err(GameError::Unbalanced(rule.name.clone()));
+            }
+        }
+        let outcome_scores = Outcome::ALL.map(|outcome| self.outcome_scores.score(outcome));
+        let most = outcome_scores.into_iter().max().unwrap_or(0);
+        if let Some(rule) = self
+            .moves
+            .iter()
+            .find(|rule| rule.score.checked_add(most).is_none())
+        {
+            return Err(GameError::ScoreOverflow(rule.name.clone()));
+        }
+        Ok(self)
+    }
+
+    /// Returns the moves that can be played in the game.
+    pub fn moves(&self) -> &[MoveRule] {
+        &self.moves
+    }
+
+    /// Returns the index of the move called `name`, if there is one.
+    pub fn move_index(&self, name: &str) -> Option<MoveIndex> {
+        self.moves.iter().position(|rule| rule.name == name)
+    }
+
+    /// Returns whether the move at index `a` beats the move at index `b`.
+    pub fn beats(&self, a: MoveIndex, b: MoveIndex) -> bool {
+        self.beats[a][b]
+    }
+
+    /// Returns the outcome of the player's move against the opponent's move.
+    pub fn outcome(&self, round: &GameRound) -> Outcome {
+        if round.player == round.opponent {
+            Outcome::Draw
+        } else if self.beats(round.player, round.opponent) {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// Returns every move the player could choose to produce the `outcome` against the `opponent` move.
+    pub fn player_moves(&self, outcome: Outcome, opponent: MoveIndex) -> Vec<MoveIndex> {
+        (0..self.moves.len())
+            .filter(|&player| self.outcome(&GameRound { player, opponent }) == outcome)
+            .collect()
+    }
+
+    /// Returns the player's score for the round.
+    ///
+    /// The round score is the total of the points scored for the player's chosen move and the points scored for
+    /// the outcome of the round, which games are checked not to overflow when they're constructed.
+    pub fn score(&self, round: &GameRound) -> Score {
+        self.moves[round.player].score + self.outcome_scores.score(self.outcome(round))
+    }
+
+    /// Returns the final score of multiple rounds of the game, or `None` if it overflows a [`Score`].
+    pub fn total_score(&self, rounds: &[GameRound]) -> Option<Score> {
+        rounds.iter().try_fold(0 as Score, |total, round| {
+            total.checked_add(self.score(round))
+        })
+    }
+
+    /// Returns how many more points the player scores than the opponent for each of the player's moves (rows)
+    /// against each of the opponent's moves (columns), in the order of [`Game::moves`].
+    ///
+    /// This is the payoff matrix of the zero-sum game of trying to outscore the opponent, which can be solved with
+    /// [`solve_zero_sum`](crate::solve_zero_sum).
+    pub fn payoff_matrix(&self) -> Vec<Vec<f64>> {
+        let n = self.moves.len();
+        (0..n)
+            .map(|player| {
+                (0..n)
+                    .map(|opponent| {
+                        let score = |player, opponent| {
+                            f64::from(self.score(&GameRound { player, opponent }))
+                        };
+                        score(player, opponent) - score(opponent, player)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::MoveCount(n) => write!(
+                f,
+                "A game needs an odd number of at least 3 moves, but {n} were given"
+            ),
+            GameError::DuplicateMove(name) => write!(f, "{name} is in the game more than once"),
+            GameError::UnknownMove(name) => write!(f, "{name} is not a move in the game"),
+            GameError::BeatsItself(name) => write!(f, "{name} can't beat itself"),
+            GameError::Undecided(a, b) => {
+                write!(f, "Exactly one of {a} and {b} must beat the other")
+            }
+            GameError::Unbalanced(name) => {
+                write!(f, "{name} must beat exactly half of the other moves")
+            }
+            GameError::ScoreOverflow(name) => {
+                write!(
+                    f,
+                    "{name}'s score overflows when added to an outcome's score"
+                )
+            }
+        }
+    }
+}
+
+impl Error for GameError {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        game::{Game, GameError, GameRound, MoveRule, OutcomeScores},
+        rounds_from_moves_strategy_guide, rounds_from_outcomes_strategy_guide, solve_zero_sum,
+        Move, Outcome, Round, Score, INPUT_FILE,
+    };
+
+    fn game_rounds(rounds: &[Round]) -> Vec<GameRound> {
+        rounds
+            .iter()
+            .map(|round| GameRound {
+                player: round.player.index(),
+                opponent: round.opponent.index(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rock_paper_scissors_preset_matches_moves() {
+        let game = Game::rock_paper_scissors();
+
+        for player in Move::ALL {
+            for opponent in Move::ALL {
+                let round = Round { player, opponent };
+                let game_round = GameRound {
+                    player: player.index(),
+                    opponent: opponent.index(),
+                };
+                assert_eq!(game.outcome(&game_round), round.outcome());
+                assert_eq!(game.score(&game_round), round.score());
+            }
+        }
+    }
+
+    #[test]
+    fn rock_paper_scissors_preset_solves_day_2() {
+        let game = Game::rock_paper_scissors();
+
+        let moves_rounds = game_rounds(&rounds_from_moves_strategy_guide(INPUT_FILE));
+        assert_eq!(game.total_score(&moves_rounds), Some(14264));
+
+        let outcomes_rounds = game_rounds(&rounds_from_outcomes_strategy_guide(INPUT_FILE));
+        assert_eq!(game.total_score(&outcomes_rounds), Some(12382));
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let spock = game.move_index("Spock").unwrap();
+        let rock = game.move_index("Rock").unwrap();
+        let lizard = game.move_index("Lizard").unwrap();
+
+        let round = GameRound {
+            player: spock,
+            opponent: rock,
+        };
+        assert_eq!(game.outcome(&round), Outcome::Win);
+        assert_eq!(game.score(&round), 5 + 6);
+        assert_eq!(
+            game.player_moves(Outcome::Win, spock),
+            vec![game.move_index("Paper").unwrap(), lizard]
+        );
+    }
+
+    #[test]
+    fn payoff_matrices() {
+        assert_eq!(
+            Game::rock_paper_scissors().payoff_matrix(),
+            crate::payoff_matrix().map(Vec::from).to_vec()
+        );
+
+        // Every move of a game with equal move scores is played equally often at equilibrium.
+        let moves = (0..5)
+            .map(|i| MoveRule::new(&format!("Move {i}"), 1))
+            .collect();
+        let game = Game::cyclic(moves, OutcomeScores::default()).unwrap();
+        let (strategy, value) = solve_zero_sum(&game.payoff_matrix()).unwrap();
+        assert!(strategy.iter().all(|p| (p - 0.2).abs() < 1e-9));
+        assert!(value.abs() < 1e-9);
+    }
+
+    #[test]
+    fn cyclic_games_are_balanced() {
+        for n in [3, 5, 101] {
+            let moves = (0..n)
+                .map(|i| MoveRule::new(&format!("Move {i}"), i + 1))
+                .collect();
+            let game = Game::cyclic(moves, OutcomeScores::default()).unwrap();
+
+            for opponent in 0..n as usize {
+                assert_eq!(
+                    game.player_moves(Outcome::Win, opponent).len(),
+                    n as usize / 2
+                );
+                assert_eq!(game.player_moves(Outcome::Draw, opponent), vec![opponent]);
+            }
+        }
+    }
+
+    #[test]
+    fn configurable_scores() {
+        let moves = vec![
+            MoveRule::new("Rock", 10),
+            MoveRule::new("Paper", 20),
+            MoveRule::new("Scissors", 30),
+        ];
+        let outcome_scores = OutcomeScores {
+            loss: 1,
+            draw: 2,
+            win: 3,
+        };
+        let game = Game::cyclic(moves, outcome_scores).unwrap();
+
+        let rounds = [
+            GameRound {
+                player: 1,
+                opponent: 0,
+            },
+            GameRound {
+                player: 0,
+                opponent: 1,
+            },
+        ];
+        assert_eq!(game.total_score(&rounds), Some((20 + 3) + (10 + 1)));
+
+        let moves = vec![
+            MoveRule::new("Rock", Score::MAX - 3),
+            MoveRule::new("Paper", Score::MAX - 3),
+            MoveRule::new("Scissors", Score::MAX - 3),
+        ];
+        let game = Game::cyclic(moves, OutcomeScores::default());
+        assert_eq!(game, Err(GameError::ScoreOverflow("Rock".to_string())));
+
+        let moves = vec![
+            MoveRule::new("Rock", Score::MAX - 6),
+            MoveRule::new("Paper", 1),
+            MoveRule::new("Scissors", 1),
+        ];
+        let game = Game::cyclic(moves, OutcomeScores::default()).unwrap();
+        let round = GameRound {
+            player: 0,
+            opponent: 2,
+        };
+        assert_eq!(game.score(&round), Score::MAX);
+        assert_eq!(game.total_score(&[round, round]), None);
+    }
+
+    #[test]
+    fn invalid_games() {
+        let moves = |names: &[&str]| names.iter().map(|name| MoveRule::new(name, 1)).collect();
+
+        assert_eq!(
+            Game::cyclic(moves(&["A", "B", "C", "D"]), OutcomeScores::default()),
+            Err(GameError::MoveCount(4))
+        );
+        assert_eq!(
+            Game::cyclic(moves(&["A", "B", "A"]), OutcomeScores::default()),
+            Err(GameError::DuplicateMove("A".to_string()))
+        );
+        assert_eq!(
+            Game::new(
+                moves(&["A", "B", "C"]),
+                &[("A", "B"), ("B", "C"), ("C", "D")],
+                OutcomeScores::default()
+            ),
+            Err(GameError::UnknownMove("D".to_string()))
+        );
+        assert_eq!(
+            Game::new(
+                moves(&["A", "B", "C"]),
+                &[("A", "B"), ("B", "C")],
+                OutcomeScores::default()
+            ),
+            Err(GameError::Undecided("A".to_string(), "C".to_string()))
+        );
+        assert_eq!(
+            Game::new(
+                moves(&["A", "B", "C"]),
+                &[("A", "B"), ("B", "C"), ("A", "C")],
+                OutcomeScores::default()
+            ),
+            Err(GameError::Unbalanced("A".to_string()))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use parse_error::ParseError;
+
+use crate::{
+    parse_moves_strategy_guide_with_cipher, parse_outcomes_strategy_guide_with_cipher,
+    rounds_from_outcomes, total_score, Cipher, Move, Outcome, Round, Score,
+};
+
+/// How the second column of a strategy guide is interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    /// The second column is the player's move.
+    Moves,
+    /// The second column is the desired outcome, and the player chooses the move producing it.
+    Outcomes,
+}
+
+/// A way of reading a strategy guide, and the total score it results in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reading {
+    pub interpretation: Interpretation,
+    pub cipher: Cipher,
+    pub score: Score,
+}
+
+impl Interpretation {
+    /// Every `Interpretation`.
+    pub const ALL: [Interpretation; 2] = [Interpretation::Moves, Interpretation::Outcomes];
+
+    /// Returns the rounds played by following the strategy guide in `input`, decoded with `cipher`.
+    pub fn rounds(&self, input: &str, cipher: &Cipher) -> Result<Vec<Round>, ParseError> {
+        match self {
+            Interpretation::Moves => parse_moves_strategy_guide_with_cipher(input, cipher),
+            Interpretation::Outcomes => Ok(rounds_from_outcomes(
+                &parse_outcomes_strategy_guide_with_cipher(input, cipher)?,
+            )),
+        }
+    }
+}
+
+/// Keeps whichever of the `error` kept so far and the new error `e` was found furthest into the input, since the
+/// reading that got furthest is the most likely to be the one the guide was written for.
+pub(crate) fn keep_furthest(error: &mut Option<ParseError>, e: ParseError) {
+    if error.as_ref().is_none_or(|error| e.offset > error.offset) {
+        *error = Some(e);
+    }
+}
+
+impl Display for Interpretation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interpretation::Moves => f.pad("moves"),
+            Interpretation::Outcomes => f.pad("outcomes"),
+        }
+    }
+}
+
+impl Display for Reading {
+    /// Describes what each letter of the second column means, e.g. "moves: X=Rock, Y=Paper, Z=Scissors".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let meanings: Vec<String> = match self.interpretation {
+            Interpretation::Moves => Move::ALL
+                .iter()
+                .zip(self.cipher.player_moves)
+                .map(|(m, letter)| format!("{}={m}", letter as char))
+                .collect(),
+            Interpretation::Outcomes => Outcome::ALL
+                .iter()
+                .zip(self.cipher.outcomes)
+                .map(|(outcome, letter)| format!("{}={outcome}", letter as char))
+                .collect(),
+        };
+        write!(f, "{}: {}", self.interpretation, meanings.join(", "))
+    }
+}
+
+/// Returns the total score of the strategy guide in `input` under every interpretation of its second column (as
+/// moves or as outcomes) and every assignment of that interpretation's letters to its values (see
+/// [`Cipher::second_column_permutations`]).
+///
+/// Interpretations whose letters the guide isn't encoded with are left out. If the guide can't be read under any
+/// interpretation, returns the error of whichever reading got furthest into the guide.
+pub fn readings(input: &str, cipher: &Cipher) -> Result<Vec<Reading>, ParseError> {
+    let mut readings = Vec::new();
+    let mut error = None;
+    for interpretation in Interpretation::ALL {
+        for cipher in cipher.second_column_permutations() {
+            match interpretation.rounds(input, &cipher) {
+                Ok(rounds) => readings.push(Reading {
+                    interpretation,
+                    cipher,
+                    score: total_score(&rounds),
+                }),
+                Err(e) => keep_furthest(&mut error, e),
+            }
+        }
+    }
+    match error {
+        Some(e) if readings.is_empty() => Err(e),
+        _ => Ok(readings),
+    }
+}
+
+/// Returns the readings of the strategy guide in `input` (see [`readings`]) that result in the `claimed` total
+/// score.
+pub fn infer_readings(
+    input: &str,
+    cipher: &Cipher,
+    claimed: Score,
+) -> Result<Vec<Reading>, ParseError> {
+    Ok(readings(input, cipher)?
+        .into_iter()
+        .filter(|reading| reading.score == claimed)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        interpretation::{infer_readings, readings, Interpretation},
+        Cipher,
+    };
+
+    static EXAMPLE_INPUT: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn every_reading_is_scored() {
+        let readings = readings(EXAMPLE_INPUT, &Cipher::default()).unwrap();
+
+        assert_eq!(readings.len(), 12);
+        assert_eq!(readings[0].interpretation, Interpretation::Moves);
+        assert_eq!(readings[0].score, 15);
+        assert_eq!(readings[6].interpretation, Interpretation::Outcomes);
+        assert_eq!(readings[6].score, 12);
+    }
+
+    #[test]
+    fn infer_puzzle_readings() {
+        let inferred = infer_readings(EXAMPLE_INPUT, &Cipher::default(), 12).unwrap();
+
+        assert!(inferred
+            .iter()
+            .any(|reading| reading.to_string() == "outcomes: X=Loss, Y=Draw, Z=Win"));
+        assert!(inferred.iter().all(|reading| reading.score == 12));
+        assert!(infer_readings(EXAMPLE_INPUT, &Cipher::default(), 1000)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn infer_moves_reading() {
+        let inferred = infer_readings(EXAMPLE_INPUT, &Cipher::default(), 24).unwrap();
+
+        assert_eq!(
+            inferred
+                .iter()
+                .map(|reading| reading.to_string())
+                .collect::<Vec<_>>(),
+            vec!["moves: Z=Rock, Y=Paper, X=Scissors"]
+        );
+    }
+
+    #[test]
+    fn infer_readings_of_one_interpretations_letters() {
+        let input = "A W\nB L\nC D\n";
+        let cipher: Cipher = "outcome=LDW".parse().unwrap();
+
+        // The guide can only be read as outcomes, under every order of their letters.
+        let readings = readings(input, &cipher).unwrap();
+        assert_eq!(readings.len(), 6);
+        assert!(readings
+            .iter()
+            .all(|reading| reading.interpretation == Interpretation::Outcomes));
+        let mut orders: Vec<_> = readings
+            .iter()
+            .map(|reading| reading.cipher.outcomes)
+            .collect();
+        orders.sort_unstable();
+        orders.dedup();
+        assert_eq!(orders.len(), 6);
+
+        let inferred = infer_readings(input, &cipher, 15).unwrap();
+        assert!(inferred
+            .iter()
+            .any(|reading| reading.to_string() == "outcomes: L=Loss, D=Draw, W=Win"));
+
+        assert!(infer_readings("A Q\n", &cipher, 15).is_err());
+        // Reading the guide as outcomes gets as far as the Q, while reading it as moves stops at the first W.
+        let error = infer_readings("A W\nB Q\n", &cipher, 15).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
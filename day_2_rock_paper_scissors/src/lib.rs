@@ -0,0 +1,321 @@
+use std::{fmt::Display, fs};
+
+pub use cipher::{score_second_column_permutations, Cipher, CipherError, CipherScores, Letters};
+pub use equilibrium::{
+    equilibrium, expected_score, expected_scores, normalize, opponent_distribution, payoff_matrix,
+    score_matrix, solve_zero_sum, Distribution, Equilibrium, ExpectedScores, UNIFORM,
+};
+pub use game::{Game, GameError, GameRound, MoveIndex, MoveRule, OutcomeScores};
+pub use interpretation::{infer_readings, readings, Interpretation, Reading};
+pub use parser::{
+    parse_moves_strategy_guide, parse_moves_strategy_guide_with_cipher,
+    parse_outcomes_strategy_guide, parse_outcomes_strategy_guide_with_cipher,
+};
+pub use planner::{plan, Constraints, Objective, Plan};
+pub use player::{
+    AlwaysRock, FrequencyCounter, GuideFollower, MarkovPredictor, Player, Rng, UniformRandom,
+};
+pub use report::{MoveSummary, RoundBreakdown, ScoreReport};
+pub use tournament::{round_robin, Leaderboard, Standing};
+
+mod cipher;
+mod equilibrium;
+mod game;
+mod interpretation;
+mod parser;
+mod planner;
+mod player;
+mod report;
+mod tournament;
+
+pub static INPUT_FILE: &str = "inputs/day2.txt";
+
+pub type Score = u32;
+
+/// Total score of a player over a tournament's games, wide enough that long tournaments don't overflow it.
+pub type TournamentScore = u64;
+
+/// Possible moves with different score values in Rock Paper Scissors.
+///
+/// Moves are deliberately not ordered: which move wins is a cyclic relation (Rock beats Scissors beats Paper
+/// beats Rock), which is expressed by [`Beats`] instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+/// The outcome and score of a round of Rock Paper Scissors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Draw = 3,
+    Loss = 0,
+    Win = 6,
+}
+
+/// The moves played by both players in a round of Rock Paper Scissors.
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    pub player: Move,
+    pub opponent: Move,
+}
+
+/// A dominance relation between values, such as which move wins a round of Rock Paper Scissors.
+///
+/// Unlike [`PartialOrd`], the relation doesn't need to be transitive, so it can be cyclic.
+pub trait Beats {
+    /// Returns true if `self` beats `other`.
+    fn defeats(&self, other: &Self) -> bool;
+}
+
+impl Move {
+    /// Every `Move`, in order of increasing score.
+    pub const ALL: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+    /// Returns the index of this `Move` in [`Move::ALL`], which is also its index in
+    /// [`Game::rock_paper_scissors`].
+    pub fn index(&self) -> MoveIndex {
+        *self as MoveIndex - 1
+    }
+
+    /// Returns which `Move` this `Move` beats in a game of Rock Paper Scissors.
+    pub fn beats(&self) -> Move {
+        match self {
+            Move::Rock => Move::Scissors,
+            Move::Paper => Move::Rock,
+            Move::Scissors => Move::Paper,
+        }
+    }
+
+    /// Returns which `Move` this `Move` loses against in a game of Rock Paper Scissors.
+    pub fn loses(&self) -> Move {
+        match self {
+            Move::Rock => Move::Paper,
+            Move::Paper => Move::Scissors,
+            Move::Scissors => Move::Rock,
+        }
+    }
+
+    pub fn score(&self) -> Score {
+        *self as Score
+    }
+}
+
+impl Beats for Move {
+    fn defeats(&self, other: &Self) -> bool {
+        self.beats() == *other
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::Rock => f.pad("Rock"),
+            Move::Paper => f.pad("Paper"),
+            Move::Scissors => f.pad("Scissors"),
+        }
+    }
+}
+
+impl Outcome {
+    /// Every `Outcome`, in order of increasing score.
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    /// Returns the score associated with a game Outcome.
+    pub fn score(&self) -> Score {
+        *self as Score
+    }
+
+    /// Returns the player's move that would produce this Outcome against `opponent_move`.
+    pub fn player_move(&self, opponent_move: &Move) -> Move {
+        match self {
+            Outcome::Draw => *opponent_move,
+            Outcome::Loss => opponent_move.beats(),
+            Outcome::Win => opponent_move.loses(),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Draw => f.pad("Draw"),
+            Outcome::Loss => f.pad("Loss"),
+            Outcome::Win => f.pad("Win"),
+        }
+    }
+}
+
+impl Round {
+    /// Returns the outcome of the player's move against the opponent's move.
+    pub fn outcome(&self) -> Outcome {
+        if self.player == self.opponent {
+            Outcome::Draw
+        } else if self.player.defeats(&self.opponent) {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// Returns the player's score for the round.
+    ///
+    /// The round score is the total of the points scored for the player's chosen
+    /// move and the score for the outcome of the game.
+    pub fn score(&self) -> Score {
+        self.player.score() + self.outcome().score()
+    }
+}
+
+/// Returns the rounds played by following the strategy guide in `input_file`, with its second column interpreted
+/// as the player's moves.
+pub fn rounds_from_moves_strategy_guide(input_file: &str) -> Vec<Round> {
+    rounds_from_strategy_guide(input_file, Interpretation::Moves)
+}
+
+/// Returns the rounds played by following the strategy guide in `input_file`, with its second column interpreted
+/// as the desired outcomes.
+pub fn rounds_from_outcomes_strategy_guide(input_file: &str) -> Vec<Round> {
+    rounds_from_strategy_guide(input_file, Interpretation::Outcomes)
+}
+
+/// Returns the rounds played by following the strategy guide in `input_file` with the puzzle's [`Cipher`], with its
+/// second column interpreted by `interpretation`.
+pub fn rounds_from_strategy_guide(input_file: &str, interpretation: Interpretation) -> Vec<Round> {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    interpretation
+        .rounds(&input, &Cipher::default())
+        .unwrap_or_else(|e| {
+            panic!("Failed to parse {interpretation} strategy guide from input\n{e}")
+        })
+}
+
+/// Returns the rounds played by choosing the player's move that produces each desired outcome.
+pub fn rounds_from_outcomes(strategy_guide: &[(Move, Outcome)]) -> Vec<Round> {
+    strategy_guide
+        .iter()
+        .map(|(opponent_move, outcome)| Round {
+            player: outcome.player_move(opponent_move),
+            opponent: *opponent_move,
+        })
+        .collect()
+}
+
+/// Returns the final score of multiple rounds of rock paper scissors.
+pub fn total_score(rounds: &[Round]) -> Score {
+    rounds.iter().map(|round| round.score()).sum()
+}
+
+#[cfg(test)]
+mod test {
+    static EXAMPLE_INPUT_FILE: &str = "inputs/day2-example.txt";
+
+    use crate::{
+        rounds_from_moves_strategy_guide, rounds_from_outcomes_strategy_guide, total_score, Beats,
+        Move, Outcome, Round, INPUT_FILE,
+    };
+
+    #[test]
+    fn solve_day_2_part_1() {
+        assert_eq!(
+            total_score(&rounds_from_moves_strategy_guide(INPUT_FILE)),
+            14264
+        )
+    }
+
+    #[test]
+    fn solve_day_2_example_part_1() {
+        assert_eq!(
+            total_score(&rounds_from_moves_strategy_guide(EXAMPLE_INPUT_FILE)),
+            15
+        )
+    }
+
+    #[test]
+    fn solve_day_2_part_2() {
+        assert_eq!(
+            total_score(&rounds_from_outcomes_strategy_guide(INPUT_FILE)),
+            12382
+        )
+    }
+
+    #[test]
+    fn solve_day_2_example_part_2() {
+        assert_eq!(
+            total_score(&rounds_from_outcomes_strategy_guide(EXAMPLE_INPUT_FILE)),
+            12
+        )
+    }
+
+    #[test]
+    fn exactly_one_move_wins_each_round() {
+        for a in Move::ALL {
+            for b in Move::ALL {
+                let relations = [a == b, a.defeats(&b), b.defeats(&a)];
+                assert_eq!(
+                    relations.iter().filter(|&&holds| holds).count(),
+                    1,
+                    "{a:?} vs {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn beats_and_loses_match_defeats() {
+        for a in Move::ALL {
+            for b in Move::ALL {
+                assert_eq!(a.beats() == b, a.defeats(&b), "{a:?} beats {b:?}");
+                assert_eq!(a.loses() == b, b.defeats(&a), "{a:?} loses to {b:?}");
+            }
+            assert_eq!(a.beats().loses(), a);
+            assert_eq!(a.loses().beats(), a);
+        }
+    }
+
+    #[test]
+    fn defeats_is_cyclic() {
+        assert!(Move::Rock.defeats(&Move::Scissors));
+        assert!(Move::Scissors.defeats(&Move::Paper));
+        assert!(Move::Paper.defeats(&Move::Rock));
+    }
+
+    #[test]
+    fn player_move_produces_outcome() {
+        for opponent in Move::ALL {
+            for outcome in Outcome::ALL {
+                let player = outcome.player_move(&opponent);
+                assert_eq!(
+                    Round { player, opponent }.outcome(),
+                    outcome,
+                    "{outcome:?} against {opponent:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn outcome_is_antisymmetric() {
+        for a in Move::ALL {
+            for b in Move::ALL {
+                let outcome = Round {
+                    player: a,
+                    opponent: b,
+                }
+                .outcome();
+                let reversed = Round {
+                    player: b,
+                    opponent: a,
+                }
+                .outcome();
+                let expected = match outcome {
+                    Outcome::Win => Outcome::Loss,
+                    Outcome::Draw => Outcome::Draw,
+                    Outcome::Loss => Outcome::Win,
+                };
+                assert_eq!(reversed, expected, "{a:?} vs {b:?}");
+            }
+        }
+    }
+}
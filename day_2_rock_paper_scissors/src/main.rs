@@ -1,182 +1,296 @@
-use std::{cmp::Ordering, fs};
+use std::{error::Error, fs, path::PathBuf, process::ExitCode};
 
-use parser::{parse_moves_strategy_guide, parse_outcomes_strategy_guide};
+use clap::{Parser, ValueEnum};
 
-mod parser;
+use day_2_rock_paper_scissors::{
+    equilibrium, expected_scores, infer_readings, normalize, opponent_distribution,
+    parse_moves_strategy_guide_with_cipher, plan, round_robin, score_second_column_permutations,
+    solve_zero_sum, total_score, AlwaysRock, Cipher, Constraints, FrequencyCounter, Game,
+    GuideFollower, Interpretation, MarkovPredictor, Objective, Player, Score, ScoreReport,
+    UniformRandom, INPUT_FILE, UNIFORM,
+};
 
-static INPUT_FILE: &str = "inputs/day2.txt";
+/// Score the elf's Rock Paper Scissors strategy guide.
+#[derive(Parser)]
+struct Args {
+    /// Path to the puzzle input.
+    #[arg(default_value = INPUT_FILE)]
+    input: PathBuf,
 
-type Score = u32;
+    /// Letters used to encode each column of the strategy guide, e.g. "opponent=ABC,player=XYZ,outcome=XYZ".
+    #[arg(long, conflicts_with = "cipher_file")]
+    cipher: Option<Cipher>,
 
-/// Possible moves with different score values in Rock Paper Scissors.
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Move {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+    /// Path to a file with the letters used to encode each column of the strategy guide (one key=LETTERS per line).
+    #[arg(long)]
+    cipher_file: Option<PathBuf>,
+
+    /// Print the total scores under every order of the letters of the second column instead of solving the puzzle.
+    #[arg(long)]
+    permutations: bool,
+
+    /// Print a breakdown of the score of every round instead of solving the puzzle.
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
+
+    /// How the second column of the strategy guide is interpreted for the report.
+    #[arg(long, value_enum, default_value_t = InterpretationArg::Moves)]
+    interpretation: InterpretationArg,
+
+    /// Print the strategies scoring the highest and lowest scores against the opponent's moves instead of solving
+    /// the puzzle.
+    #[arg(long)]
+    plan: bool,
+
+    /// Most rounds the planned strategies may win.
+    #[arg(long, requires = "plan")]
+    max_wins: Option<usize>,
+
+    /// Most rounds in a row the planned strategies may play the same move.
+    #[arg(long, requires = "plan")]
+    max_repeats: Option<usize>,
+
+    /// Exact number of rounds the planned strategies must draw.
+    #[arg(long, requires = "plan")]
+    draws: Option<usize>,
+
+    /// Print the equilibrium mixed strategy and expected scores per round against uniform and guide-derived
+    /// opponents instead of solving the puzzle.
+    #[arg(long)]
+    equilibrium: bool,
+
+    /// Relative weights of the opponent playing Rock, Paper and Scissors, e.g. "1,1,2", to also print the
+    /// expected scores per round against.
+    #[arg(long, requires = "equilibrium", value_delimiter = ',')]
+    opponent_weights: Option<Vec<f64>>,
+
+    /// Print the equilibrium mixed strategy of outscoring the opponent in a preset game instead of solving the
+    /// puzzle.
+    #[arg(long, value_enum)]
+    game: Option<GameArg>,
+
+    /// Print which interpretations of the second column (as moves or outcomes, with its letters in any order)
+    /// result in this total score instead of solving the puzzle.
+    #[arg(long)]
+    claimed_score: Option<Score>,
+
+    /// Print the leaderboard of a round-robin tournament between the built-in bots (including one following the
+    /// strategy guide's moves), where every pair of bots plays this many games, instead of solving the puzzle.
+    #[arg(long)]
+    tournament: Option<usize>,
+
+    /// Number of rounds in each tournament game.
+    #[arg(long, requires = "tournament", default_value_t = 1000)]
+    rounds: usize,
+
+    /// Seed for the tournament's random bot.
+    #[arg(long, requires = "tournament", default_value_t = 0)]
+    seed: u64,
 }
 
-/// The outcome and score of a round of Rock Paper Scissors.
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Outcome {
-    Draw = 3,
-    Loss = 0,
-    Win = 6,
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Csv,
 }
 
-/// The moves played by both players in a round of Rock Paper Scissors.
-#[derive(Debug, PartialEq)]
-struct Round {
-    player: Move,
-    opponent: Move,
+#[derive(Clone, Copy, ValueEnum)]
+enum InterpretationArg {
+    /// The second column is the player's move.
+    Moves,
+    /// The second column is the desired outcome.
+    Outcomes,
 }
 
-impl Move {
-    /// Returns which `Move` this `Move` beats in a game of Rock Paper Scissors.
-    fn beats(&self) -> Move {
-        match self {
-            Move::Rock => Move::Scissors,
-            Move::Paper => Move::Rock,
-            Move::Scissors => Move::Paper,
-        }
-    }
+#[derive(Clone, Copy, ValueEnum)]
+enum GameArg {
+    /// Rock Paper Scissors, scored as in the puzzle.
+    Rps,
+    /// Rock Paper Scissors Lizard Spock, with moves scored 1 to 5 in that order.
+    Rpsls,
+}
 
-    /// Returns which `Move` this `Move` loses against in a game of Rock Paper Scissors.
-    fn loses(&self) -> Move {
-        match self {
-            Move::Rock => Move::Paper,
-            Move::Paper => Move::Scissors,
-            Move::Scissors => Move::Rock,
+impl From<GameArg> for Game {
+    fn from(game: GameArg) -> Self {
+        match game {
+            GameArg::Rps => Game::rock_paper_scissors(),
+            GameArg::Rpsls => Game::rock_paper_scissors_lizard_spock(),
         }
     }
-
-    fn score(&self) -> Score {
-        *self as Score
-    }
 }
 
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (Move::Rock, Move::Paper) => Some(Ordering::Less),
-            (Move::Rock, Move::Scissors) => Some(Ordering::Greater),
-            (Move::Paper, Move::Rock) => Some(Ordering::Greater),
-            (Move::Paper, Move::Scissors) => Some(Ordering::Less),
-            (Move::Scissors, Move::Rock) => Some(Ordering::Less),
-            (Move::Scissors, Move::Paper) => Some(Ordering::Greater),
-            _ => Some(Ordering::Equal),
+impl From<InterpretationArg> for Interpretation {
+    fn from(interpretation: InterpretationArg) -> Self {
+        match interpretation {
+            InterpretationArg::Moves => Interpretation::Moves,
+            InterpretationArg::Outcomes => Interpretation::Outcomes,
         }
     }
 }
 
-impl Outcome {
-    /// Returns the score associated with a game Outcome.
-    fn score(&self) -> Score {
-        *self as Score
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    if let Some(game) = args.game {
+        let game = Game::from(game);
+        let (strategy, value) = solve_zero_sum(&game.payoff_matrix())
+            .ok_or("The game has no payoff matrix to solve")?;
+        let strategy: Vec<_> = game
+            .moves()
+            .iter()
+            .zip(strategy)
+            .map(|(rule, p)| format!("{} {p:.4}", rule.name))
+            .collect();
+        println!(
+            "Equilibrium strategy: {} (value {value:.4})",
+            strategy.join(", ")
+        );
+        return Ok(());
     }
 
-    /// Returns the player's move that would produce this Outcome against `opponent_move`.
-    fn player_move(&self, opponent_move: &Move) -> Move {
-        match self {
-            Outcome::Draw => *opponent_move,
-            Outcome::Loss => opponent_move.beats(),
-            Outcome::Win => opponent_move.loses(),
+    let cipher = match (args.cipher, args.cipher_file) {
+        (Some(cipher), _) => cipher,
+        (None, Some(cipher_file)) => fs::read_to_string(cipher_file)?.parse()?,
+        (None, None) => Cipher::default(),
+    };
+    let input = fs::read_to_string(&args.input)?;
+
+    if args.permutations {
+        println!("Second column letters for Rock, Paper and Scissors, and for Loss, Draw and Win:");
+        println!("moves | moves score | outcomes | outcomes score");
+        for scores in score_second_column_permutations(&input, &cipher)? {
+            println!(
+                "{:5} | {:>11} | {:8} | {:>14}",
+                String::from_utf8_lossy(&scores.cipher.player_moves),
+                describe_score(scores.moves_score),
+                String::from_utf8_lossy(&scores.cipher.outcomes),
+                describe_score(scores.outcomes_score)
+            );
         }
+        return Ok(());
     }
-}
 
-impl Round {
-    /// Returns the outcome of the player's move against the opponent's move.
-    fn outcome(&self) -> Outcome {
-        if self.player == self.opponent {
-            Outcome::Draw
-        } else if self.player > self.opponent {
-            Outcome::Win
-        } else {
-            Outcome::Loss
+    if let Some(claimed) = args.claimed_score {
+        let readings = infer_readings(&input, &cipher, claimed)?;
+        if readings.is_empty() {
+            println!("No interpretation of the strategy guide results in a score of {claimed}.");
+        }
+        for reading in readings {
+            println!("{reading}");
         }
+        return Ok(());
     }
 
-    /// Returns the player's score for the round.
-    ///
-    /// The round score is the total of the points scored for the player's chosen
-    /// move and the score for the outcome of the game.
-    fn score(&self) -> Score {
-        self.player.score() + self.outcome().score()
+    if let Some(games) = args.tournament {
+        let guide = parse_moves_strategy_guide_with_cipher(&input, &cipher)?;
+        let mut players: Vec<Box<dyn Player>> = vec![
+            Box::new(AlwaysRock),
+            Box::new(UniformRandom::new(args.seed)),
+            Box::new(FrequencyCounter),
+            Box::new(MarkovPredictor),
+        ];
+        if let Some(follower) = GuideFollower::new(&guide) {
+            players.push(Box::new(follower));
+        }
+        print!("{}", round_robin(&mut players, games, args.rounds));
+        return Ok(());
     }
-}
 
-fn rounds_from_moves_strategy_guide(input_file: &str) -> Vec<Round> {
-    let input = fs::read_to_string(input_file).expect("Failed to read input file");
-    parse_moves_strategy_guide(&input)
-}
+    if args.equilibrium {
+        let rounds = parse_moves_strategy_guide_with_cipher(&input, &cipher)?;
+        let mut opponents = vec![
+            ("uniform", Some(UNIFORM)),
+            ("guide", opponent_distribution(&rounds)),
+        ];
+        if let Some(weights) = args.opponent_weights {
+            let distribution = <[f64; 3]>::try_from(weights)
+                .ok()
+                .and_then(normalize)
+                .ok_or("Opponent weights must be 3 non-negative numbers that aren't all zero")?;
+            opponents.push(("biased", Some(distribution)));
+        }
+        let equilibrium = equilibrium();
+        println!(
+            "Equilibrium strategy: Rock {:.4}, Paper {:.4}, Scissors {:.4} (value {:.4})",
+            equilibrium.strategy[0],
+            equilibrium.strategy[1],
+            equilibrium.strategy[2],
+            equilibrium.value
+        );
 
-fn rounds_from_outcomes_strategy_guide(input_file: &str) -> Vec<Round> {
-    let input = fs::read_to_string(input_file).expect("Failed to read input file");
-    let strategy_guide = parse_outcomes_strategy_guide(&input);
-    strategy_guide
-        .iter()
-        .map(|(opponent_move, outcome)| Round {
-            player: outcome.player_move(opponent_move),
-            opponent: *opponent_move,
-        })
-        .collect()
-}
+        println!("opponent | equilibrium | uniform | best response");
+        for (name, distribution) in opponents {
+            let Some(distribution) = distribution else {
+                continue;
+            };
+            let scores = expected_scores(&distribution);
+            println!(
+                "{name:8} | {:>11.4} | {:>7.4} | {} ({:.4})",
+                scores.equilibrium, scores.uniform, scores.best_response.0, scores.best_response.1
+            );
+        }
+        return Ok(());
+    }
 
-/// Returns the final score of multiple rounds of rock paper scissors.
-fn total_score(rounds: Vec<Round>) -> Score {
-    rounds.iter().map(|round| round.score()).sum()
-}
+    if args.plan {
+        let opponent: Vec<_> = parse_moves_strategy_guide_with_cipher(&input, &cipher)?
+            .iter()
+            .map(|round| round.opponent)
+            .collect();
+        let constraints = Constraints {
+            max_wins: args.max_wins,
+            max_repeats: args.max_repeats,
+            draws: args.draws,
+        };
+        for (name, objective) in [
+            ("Highest", Objective::Highest),
+            ("Lowest", Objective::Lowest),
+        ] {
+            match plan(&opponent, &constraints, objective) {
+                Some(plan) => println!(
+                    "{name} achievable score is {} by playing {}",
+                    plan.score,
+                    plan.moves
+                        .iter()
+                        .map(|player| cipher.player_moves[player.index()] as char)
+                        .collect::<String>()
+                ),
+                None => println!("{name} score: no strategy keeps to the constraints"),
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(format) = args.report {
+        let interpretation = Interpretation::from(args.interpretation);
+        let report = ScoreReport::new(&interpretation.rounds(&input, &cipher)?);
+        match format {
+            ReportFormat::Table => print!("{report}"),
+            ReportFormat::Csv => print!("{}", report.to_csv()),
+        }
+        return Ok(());
+    }
 
-fn main() {
     println!(
         "Interpreting the strategy guide as opponent moves to chosen moves would result in a final score of {}.",
-         total_score(rounds_from_moves_strategy_guide(INPUT_FILE))
+        total_score(&Interpretation::Moves.rounds(&input, &cipher)?)
     );
     println!(
         "Interpreting the strategy guide as opponent moves to desired outcomes would result in a final score of {}.",
-        total_score(rounds_from_outcomes_strategy_guide(INPUT_FILE))
-    )
+        total_score(&Interpretation::Outcomes.rounds(&input, &cipher)?)
+    );
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    static EXAMPLE_INPUT_FILE: &str = "inputs/day2-example.txt";
-
-    use crate::{
-        rounds_from_moves_strategy_guide, rounds_from_outcomes_strategy_guide, total_score,
-        INPUT_FILE,
-    };
-
-    #[test]
-    fn solve_day_2_part_1() {
-        assert_eq!(
-            total_score(rounds_from_moves_strategy_guide(INPUT_FILE)),
-            14264
-        )
-    }
-
-    #[test]
-    fn solve_day_2_example_part_1() {
-        assert_eq!(
-            total_score(rounds_from_moves_strategy_guide(EXAMPLE_INPUT_FILE)),
-            15
-        )
-    }
-
-    #[test]
-    fn solve_day_2_part_2() {
-        assert_eq!(
-            total_score(rounds_from_outcomes_strategy_guide(INPUT_FILE)),
-            12382
-        )
-    }
+/// Formats a total score, or `-` when the strategy guide can't be read that way.
+fn describe_score(score: Option<Score>) -> String {
+    score.map_or_else(|| "-".to_string(), |score| score.to_string())
+}
 
-    #[test]
-    fn solve_day_2_example_part_2() {
-        assert_eq!(
-            total_score(rounds_from_outcomes_strategy_guide(EXAMPLE_INPUT_FILE)),
-            12
-        )
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
     }
 }
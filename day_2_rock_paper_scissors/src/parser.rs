@@ -1,34 +1,20 @@
 #![allow(clippy::result_large_err)]
+// `switch!` expands to `if let Some(..) = ..ok()` when matching on a parser.
+#![allow(clippy::match_result_ok)]
 
 use pear::{
     combinators::*,
     input::{Cursor, Pear},
-    macros::{parse_error, parser},
+    macros::{parse_error, parser, switch},
     parsers::*,
 };
 
-use crate::{Move, Outcome, Round};
-
-fn decode_move(&byte: &u8) -> std::result::Result<Move, &'static str> {
-    match byte {
-        b'A' => Ok(Move::Rock),
-        b'B' => Ok(Move::Paper),
-        b'C' => Ok(Move::Scissors),
-        b'X' => Ok(Move::Rock),
-        b'Y' => Ok(Move::Paper),
-        b'Z' => Ok(Move::Scissors),
-        _ => Err("{byte} is not a valid encoding of a Rocker Paper Scissors Move"),
-    }
-}
+use parse_error::ParseError;
 
-fn decode_outcome(&byte: &u8) -> std::result::Result<Outcome, &'static str> {
-    match byte {
-        b'X' => Ok(Outcome::Loss),
-        b'Y' => Ok(Outcome::Draw),
-        b'Z' => Ok(Outcome::Win),
-        _ => Err("{byte} is not a valid encoding of a Rocker Paper Scissors game Outcome"),
-    }
-}
+use crate::{
+    cipher::{decode, describe, Cipher, Letters},
+    Move, Outcome, Round,
+};
 
 /// Use cursor over bytes as parser Input.
 type Input<'a> = Pear<Cursor<&'a [u8]>>;
@@ -38,70 +24,109 @@ type Input<'a> = Pear<Cursor<&'a [u8]>>;
 /// See [`pear::macros::parser`] for details.
 type Result<'a, T> = pear::input::Result<T, Input<'a>>;
 
-#[inline]
-fn is_move_byte(&byte: &u8) -> bool {
-    matches!(byte, b'A'..=b'C') || matches!(byte, b'X'..=b'Z')
-}
-
-#[inline]
-fn is_outcome_byte(&byte: &u8) -> bool {
-    matches!(byte, b'X'..=b'Z')
-}
-
 #[inline]
 fn is_newline_byte(&byte: &u8) -> bool {
     byte == b'\n'
 }
 
+/// Parses a move encoded by one of the `letters`, given in the order of [`Move::ALL`].
 #[parser]
-fn encoded_move<'a>(input: &mut Input<'a>) -> Result<'a, Move> {
-    decode_move(&eat_if(is_move_byte)?).or_else(|e| parse_error!("{}", e)?)
+fn encoded_move<'a>(input: &mut Input<'a>, letters: &Letters) -> Result<'a, Move> {
+    switch! {
+        byte@eat_if(|byte: &u8| letters.contains(byte)) => Move::ALL[decode(letters, byte).expect("byte is a letter")],
+        _ => parse_error!("expected an encoded move ({})", describe(letters))?,
+    }
 }
 
+/// Parses an outcome encoded by one of the `letters`, given in the order of [`Outcome::ALL`].
 #[parser]
-fn encoded_outcome<'a>(input: &mut Input<'a>) -> Result<'a, Outcome> {
-    decode_outcome(&eat_if(is_outcome_byte)?).or_else(|e| parse_error!("{}", e)?)
+fn encoded_outcome<'a>(input: &mut Input<'a>, letters: &Letters) -> Result<'a, Outcome> {
+    switch! {
+        byte@eat_if(|byte: &u8| letters.contains(byte)) => Outcome::ALL[decode(letters, byte).expect("byte is a letter")],
+        _ => parse_error!("expected an encoded outcome ({})", describe(letters))?,
+    }
 }
 
 /// Parse the line as the opponent's move and the player's move.
 #[parser]
-fn round_moves_line<'a>(input: &mut Input<'a>) -> Result<'a, Round> {
-    let opponent = encoded_move()?;
+fn round_moves_line<'a>(input: &mut Input<'a>, cipher: &Cipher) -> Result<'a, Round> {
+    let opponent = encoded_move(&cipher.opponent_moves)?;
     eat(b' ')?;
-    let player = encoded_move()?;
+    let player = encoded_move(&cipher.player_moves)?;
     eat_if(is_newline_byte)?;
     Round { player, opponent }
 }
 
 /// Parse the line as the opponent's move and the desired game outcome.
 #[parser]
-fn round_outcome_line<'a>(input: &mut Input<'a>) -> Result<'a, (Move, Outcome)> {
-    let opponent_move = encoded_move()?;
+fn round_outcome_line<'a>(input: &mut Input<'a>, cipher: &Cipher) -> Result<'a, (Move, Outcome)> {
+    let opponent_move = encoded_move(&cipher.opponent_moves)?;
     eat(b' ')?;
-    let outcome = encoded_outcome()?;
+    let outcome = encoded_outcome(&cipher.outcomes)?;
     eat_if(is_newline_byte)?;
     (opponent_move, outcome)
 }
 
 #[parser]
-fn moves_strategy_guide<'a>(input: &mut Input<'a>) -> Result<'a, Vec<Round>> {
-    let rounds: Vec<Round> = collect(round_moves_line)?;
+fn moves_strategy_guide<'a>(input: &mut Input<'a>, cipher: &Cipher) -> Result<'a, Vec<Round>> {
+    let rounds: Vec<Round> = collect(|input| round_moves_line(input, cipher))?;
     rounds
 }
 
 #[parser]
-fn outcomes_strategy_guide<'a>(input: &mut Input<'a>) -> Result<'a, Vec<(Move, Outcome)>> {
-    let rounds: Vec<(Move, Outcome)> = collect(round_outcome_line)?;
+fn outcomes_strategy_guide<'a>(
+    input: &mut Input<'a>,
+    cipher: &Cipher,
+) -> Result<'a, Vec<(Move, Outcome)>> {
+    let rounds: Vec<(Move, Outcome)> = collect(|input| round_outcome_line(input, cipher))?;
     rounds
 }
 
-pub(crate) fn parse_moves_strategy_guide(input: &str) -> Vec<Round> {
+/// Parse `input` as rounds of the opponent's move and the player's move, using the puzzle's [`Cipher`].
+pub fn parse_moves_strategy_guide(input: &str) -> std::result::Result<Vec<Round>, ParseError> {
+    parse_moves_strategy_guide_with_cipher(input, &Cipher::default())
+}
+
+/// Parse `input` as rounds of the opponent's move and the player's move, decoded with `cipher`.
+pub fn parse_moves_strategy_guide_with_cipher(
+    input: &str,
+    cipher: &Cipher,
+) -> std::result::Result<Vec<Round>, ParseError> {
     let mut cursor = Input::new(input.as_bytes());
-    moves_strategy_guide(&mut cursor).expect("Failed to parse moves strategy guide from input")
+    moves_strategy_guide(&mut cursor, cipher).map_err(|e| ParseError::from_pear(input, &e))
 }
 
-pub(crate) fn parse_outcomes_strategy_guide(input: &str) -> Vec<(Move, Outcome)> {
+/// Parse `input` as rounds of the opponent's move and the desired outcome, using the puzzle's [`Cipher`].
+pub fn parse_outcomes_strategy_guide(
+    input: &str,
+) -> std::result::Result<Vec<(Move, Outcome)>, ParseError> {
+    parse_outcomes_strategy_guide_with_cipher(input, &Cipher::default())
+}
+
+/// Parse `input` as rounds of the opponent's move and the desired outcome, decoded with `cipher`.
+pub fn parse_outcomes_strategy_guide_with_cipher(
+    input: &str,
+    cipher: &Cipher,
+) -> std::result::Result<Vec<(Move, Outcome)>, ParseError> {
     let mut cursor = Input::new(input.as_bytes());
-    outcomes_strategy_guide(&mut cursor)
-        .expect("Failed to parse outcomes strategy guide from input")
+    outcomes_strategy_guide(&mut cursor, cipher).map_err(|e| ParseError::from_pear(input, &e))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{parse_moves_strategy_guide, parse_outcomes_strategy_guide};
+
+    #[test]
+    fn invalid_move_error_location() {
+        let error = parse_moves_strategy_guide("A Y\nB X\nC W\n").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (10, 3, 3));
+        assert_eq!(error.snippet, "C W");
+    }
+
+    #[test]
+    fn invalid_outcome_error_location() {
+        let error = parse_outcomes_strategy_guide("A Y\nB C\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "expected an encoded outcome (X, Y or Z)");
+    }
 }
@@ -0,0 +1,406 @@
+use crate::{Move, Outcome, Round, Score};
+
+/// Optional limits on the player's moves when planning a strategy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Most rounds the player may win.
+    pub max_wins: Option<usize>,
+    /// Most rounds in a row the player may play the same move, e.g. `Some(2)` forbids playing a move 3 times
+    /// in a row.
+    pub max_repeats: Option<usize>,
+    /// Exact number of rounds that must end in a draw.
+    pub draws: Option<usize>,
+}
+
+/// Whether a plan should score as many or as few points as possible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    Highest,
+    Lowest,
+}
+
+/// A strategy of the player's `moves` for each round, and the final `score` it achieves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub score: Score,
+    pub moves: Vec<Move>,
+}
+
+impl Plan {
+    /// Returns the rounds played by following the plan against the `opponent` moves it was planned for.
+    pub fn rounds(&self, opponent: &[Move]) -> Vec<Round> {
+        self.moves
+            .iter()
+            .zip(opponent)
+            .map(|(&player, &opponent)| Round { player, opponent })
+            .collect()
+    }
+}
+
+/// The dimensions of the states tracked while planning. Each constraint that isn't given collapses its
+/// dimension to a single value, so only the constraints in use cost time and memory.
+struct States {
+    wins: usize,
+    draws: usize,
+    /// The last move played, where index 3 is "no move played yet".
+    last_moves: usize,
+    runs: usize,
+}
+
+impl States {
+    fn new(constraints: &Constraints, rounds: usize) -> States {
+        // More wins, draws or repeats than rounds can't be reached, so there's no need to track them.
+        States {
+            wins: constraints.max_wins.map_or(1, |wins| wins.min(rounds) + 1),
+            draws: constraints.draws.map_or(1, |draws| draws.min(rounds) + 1),
+            last_moves: if constraints.max_repeats.is_some() {
+                4
+            } else {
+                1
+            },
+            runs: constraints
+                .max_repeats
+                .map_or(1, |repeats| repeats.min(rounds) + 1),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.wins * self.draws * self.last_moves * self.runs
+    }
+
+    fn index(&self, wins: usize, draws: usize, last_move: usize, run: usize) -> usize {
+        ((wins * self.draws + draws) * self.last_moves + last_move) * self.runs + run
+    }
+
+    /// Returns the wins, draws, last move and run of the state at `index`.
+    fn decode(&self, index: usize) -> (usize, usize, usize, usize) {
+        let run = index % self.runs;
+        let last_move = index / self.runs % self.last_moves;
+        let draws = index / (self.runs * self.last_moves) % self.draws;
+        let wins = index / (self.runs * self.last_moves * self.draws);
+        (wins, draws, last_move, run)
+    }
+}
+
+/// How each state was reached in a round: the previous state and the move played.
+type ReachedBy = Vec<Option<(usize, Move)>>;
+
+/// Plans rounds one at a time, keeping to the constraints.
+struct Planner<'a> {
+    constraints: &'a Constraints,
+    objective: Objective,
+    states: States,
+}
+
+impl Planner<'_> {
+    /// Returns true if `score` is better than the score `than` of another way of reaching the same state.
+    fn better(&self, score: Score, than: Option<Score>) -> bool {
+        match (self.objective, than) {
+            (_, None) => true,
+            (Objective::Highest, Some(than)) => score > than,
+            (Objective::Lowest, Some(than)) => score < than,
+        }
+    }
+
+    /// Returns the best score of each state after playing a round against `opponent_move`, given the best score
+    /// of each state before it. Records how each state was reached in `reached_by`, if given.
+    fn advance(
+        &self,
+        scores: &[Option<Score>],
+        opponent_move: Move,
+        mut reached_by: Option<&mut ReachedBy>,
+    ) -> Vec<Option<Score>> {
+        let constraints = self.constraints;
+        let mut next_scores: Vec<Option<Score>> = vec![None; self.states.len()];
+
+        for (index, score) in scores.iter().enumerate() {
+            let Some(score) = *score else {
+                continue;
+            };
+            let (wins, draws, last_move, run) = self.states.decode(index);
+
+            for player in Move::ALL {
+                let round = Round {
+                    player,
+                    opponent: opponent_move,
+                };
+                let outcome = round.outcome();
+
+                let next_wins = match constraints.max_wins {
+                    Some(max) => match wins + usize::from(outcome == Outcome::Win) {
+                        wins if wins > max => continue,
+                        wins => wins,
+                    },
+                    None => 0,
+                };
+                let next_draws = match constraints.draws {
+                    Some(max) => match draws + usize::from(outcome == Outcome::Draw) {
+                        draws if draws > max => continue,
+                        draws => draws,
+                    },
+                    None => 0,
+                };
+                let (next_last_move, next_run) = match constraints.max_repeats {
+                    Some(max) => {
+                        let run = if player.index() == last_move {
+                            run + 1
+                        } else {
+                            1
+                        };
+                        if run > max {
+                            continue;
+                        }
+                        (player.index(), run)
+                    }
+                    None => (0, 0),
+                };
+                let next_index = self
+                    .states
+                    .index(next_wins, next_draws, next_last_move, next_run);
+
+                let next_score = score + round.score();
+                if self.better(next_score, next_scores[next_index]) {
+                    next_scores[next_index] = Some(next_score);
+                    if let Some(reached_by) = reached_by.as_deref_mut() {
+                        reached_by[next_index] = Some((index, player));
+                    }
+                }
+            }
+        }
+        next_scores
+    }
+}
+
+/// Returns the strategy scoring the highest or lowest total score (depending on the `objective`) against the
+/// `opponent` moves while keeping to the `constraints`, or `None` if no strategy keeps to them.
+///
+/// This is a dynamic program over the rounds, tracking only the state needed by the constraints in use (the wins
+/// and draws so far, and the last move and how many times in a row it was played), so it takes time proportional
+/// to the number of rounds times the number of those states. With every constraint given, that's the possible wins
+/// times draws times last moves times runs of the last move, with each capped by the number of rounds.
+///
+/// To recover the moves without remembering how every state of every round was reached, the scores are kept every
+/// √rounds rounds, and the rounds between them are played again one stretch at a time, so memory is proportional to
+/// √rounds times the number of states.
+pub fn plan(opponent: &[Move], constraints: &Constraints, objective: Objective) -> Option<Plan> {
+    if constraints
+        .draws
+        .is_some_and(|draws| draws > opponent.len())
+    {
+        return None;
+    }
+    let planner = Planner {
+        constraints,
+        objective,
+        states: States::new(constraints, opponent.len()),
+    };
+    let states = &planner.states;
+
+    // The best score of each state after the rounds played so far, kept at the start of each stretch of rounds.
+    let stretch = opponent.len().isqrt().max(1);
+    let mut scores: Vec<Option<Score>> = vec![None; states.len()];
+    let start_move = states.last_moves - 1;
+    scores[states.index(0, 0, start_move, 0)] = Some(0);
+    let mut checkpoints: Vec<Vec<Option<Score>>> = Vec::new();
+    for stretch_moves in opponent.chunks(stretch) {
+        checkpoints.push(scores.clone());
+        for &opponent_move in stretch_moves {
+            scores = planner.advance(&scores, opponent_move, None);
+        }
+    }
+
+    // Pick the best final state, which must have exactly the required number of draws.
+    let mut end: Option<(usize, Score)> = None;
+    for (index, score) in scores.iter().enumerate() {
+        let Some(score) = *score else {
+            continue;
+        };
+        if constraints
+            .draws
+            .is_some_and(|draws| states.decode(index).1 != draws)
+        {
+            continue;
+        }
+        if planner.better(score, end.map(|(_, best)| best)) {
+            end = Some((index, score));
+        }
+    }
+    let (mut index, score) = end?;
+
+    // Play each stretch again from its checkpoint, last to first, recording how each state was reached so the
+    // moves can be recovered by walking back through them.
+    let mut moves = Vec::with_capacity(opponent.len());
+    for (stretch_moves, checkpoint) in opponent.chunks(stretch).zip(checkpoints).rev() {
+        let mut scores = checkpoint;
+        let mut reached_by: Vec<ReachedBy> = Vec::with_capacity(stretch_moves.len());
+        for &opponent_move in stretch_moves {
+            let mut round_reached_by = vec![None; states.len()];
+            scores = planner.advance(&scores, opponent_move, Some(&mut round_reached_by));
+            reached_by.push(round_reached_by);
+        }
+        for reached_by in reached_by.iter().rev() {
+            let (previous, player) =
+                reached_by[index].expect("reachable states have a predecessor");
+            moves.push(player);
+            index = previous;
+        }
+    }
+    moves.reverse();
+
+    Some(Plan { score, moves })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        planner::{plan, Constraints, Objective},
+        rounds_from_moves_strategy_guide, total_score, Move, Outcome, Round, Score, INPUT_FILE,
+    };
+
+    /// Returns the scores of every strategy against `opponent` keeping to the `constraints`, by trying them all.
+    fn brute_force_scores(opponent: &[Move], constraints: &Constraints) -> Vec<Score> {
+        let mut strategies: Vec<Vec<Move>> = vec![vec![]];
+        for _ in opponent {
+            strategies = strategies
+                .into_iter()
+                .flat_map(|strategy| {
+                    Move::ALL.iter().map(move |&player| {
+                        let mut strategy = strategy.clone();
+                        strategy.push(player);
+                        strategy
+                    })
+                })
+                .collect();
+        }
+
+        strategies
+            .into_iter()
+            .filter(|strategy| keeps_to(strategy, opponent, constraints))
+            .map(|strategy| total_score(&rounds(&strategy, opponent)))
+            .collect()
+    }
+
+    fn rounds(strategy: &[Move], opponent: &[Move]) -> Vec<Round> {
+        strategy
+            .iter()
+            .zip(opponent)
+            .map(|(&player, &opponent)| Round { player, opponent })
+            .collect()
+    }
+
+    fn keeps_to(strategy: &[Move], opponent: &[Move], constraints: &Constraints) -> bool {
+        let rounds = rounds(strategy, opponent);
+        let count = |outcome| rounds.iter().filter(|r| r.outcome() == outcome).count();
+        let longest_run = strategy
+            .chunk_by(|a, b| a == b)
+            .map(|run| run.len())
+            .max()
+            .unwrap_or(0);
+
+        constraints
+            .max_wins
+            .is_none_or(|max| count(Outcome::Win) <= max)
+            && constraints
+                .draws
+                .is_none_or(|draws| count(Outcome::Draw) == draws)
+            && constraints.max_repeats.is_none_or(|max| longest_run <= max)
+    }
+
+    #[test]
+    fn plans_match_brute_force() {
+        use Move::*;
+        let opponent = [Rock, Rock, Rock, Paper, Scissors, Scissors, Rock];
+
+        for max_wins in [None, Some(0), Some(2)] {
+            for max_repeats in [None, Some(1), Some(2)] {
+                for draws in [None, Some(0), Some(3), Some(7)] {
+                    let constraints = Constraints {
+                        max_wins,
+                        max_repeats,
+                        draws,
+                    };
+                    let scores = brute_force_scores(&opponent, &constraints);
+
+                    for (objective, expected) in [
+                        (Objective::Highest, scores.iter().max()),
+                        (Objective::Lowest, scores.iter().min()),
+                    ] {
+                        let plan = plan(&opponent, &constraints, objective);
+                        assert_eq!(
+                            plan.as_ref().map(|plan| plan.score),
+                            expected.copied(),
+                            "{objective:?} with {constraints:?}"
+                        );
+                        if let Some(plan) = plan {
+                            assert!(keeps_to(&plan.moves, &opponent, &constraints));
+                            assert_eq!(total_score(&plan.rounds(&opponent)), plan.score);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn constraints_beyond_the_rounds() {
+        use Move::*;
+        let opponent = [Rock, Paper, Paper, Scissors, Rock];
+
+        let constraints = Constraints {
+            max_wins: Some(1000),
+            max_repeats: Some(usize::MAX),
+            draws: Some(2),
+        };
+        let scores = brute_force_scores(&opponent, &constraints);
+        let highest = plan(&opponent, &constraints, Objective::Highest).unwrap();
+        assert_eq!(Some(&highest.score), scores.iter().max());
+
+        let constraints = Constraints {
+            draws: Some(800),
+            ..constraints
+        };
+        assert_eq!(plan(&opponent, &constraints, Objective::Highest), None);
+    }
+
+    #[test]
+    fn constrained_plans_of_the_whole_guide() {
+        let opponent: Vec<Move> = rounds_from_moves_strategy_guide(INPUT_FILE)
+            .iter()
+            .map(|round| round.opponent)
+            .take(120)
+            .collect();
+        let constraints = Constraints {
+            max_wins: Some(40),
+            max_repeats: Some(3),
+            draws: Some(30),
+        };
+
+        for objective in [Objective::Highest, Objective::Lowest] {
+            let plan = plan(&opponent, &constraints, objective).unwrap();
+            assert_eq!(plan.moves.len(), opponent.len());
+            assert!(keeps_to(&plan.moves, &opponent, &constraints));
+            assert_eq!(total_score(&plan.rounds(&opponent)), plan.score);
+        }
+    }
+
+    #[test]
+    fn unconstrained_plans_win_or_lose_every_round() {
+        let opponent: Vec<Move> = rounds_from_moves_strategy_guide(INPUT_FILE)
+            .iter()
+            .map(|round| round.opponent)
+            .collect();
+
+        let highest = plan(&opponent, &Constraints::default(), Objective::Highest).unwrap();
+        let lowest = plan(&opponent, &Constraints::default(), Objective::Lowest).unwrap();
+
+        assert!(highest
+            .rounds(&opponent)
+            .iter()
+            .all(|round| round.outcome() == Outcome::Win));
+        assert!(lowest
+            .rounds(&opponent)
+            .iter()
+            .all(|round| round.outcome() == Outcome::Loss));
+        assert!(lowest.score <= 12382 && 14264 <= highest.score);
+    }
+}
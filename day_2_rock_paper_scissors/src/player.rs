@@ -0,0 +1,237 @@
+use crate::{Move, Round};
+
+/// A strategy for choosing moves in a game of Rock Paper Scissors.
+pub trait Player {
+    /// Returns the name of the player shown on a leaderboard.
+    fn name(&self) -> String;
+
+    /// Returns the move to play next, given the `history` of rounds played so far in the current game.
+    ///
+    /// Each round in the `history` is from this player's perspective, so `round.player` is the move this player
+    /// played and `round.opponent` is the move played against it.
+    fn choose(&mut self, history: &[Round]) -> Move;
+}
+
+/// Small seedable pseudorandom number generator (SplitMix64), so games with random players can be replayed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Construct an `Rng` producing the sequence of numbers determined by `seed`.
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    /// Returns the next pseudorandom number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a move chosen uniformly at random.
+    pub fn next_move(&mut self) -> Move {
+        Move::ALL[(self.next_u64() % 3) as usize]
+    }
+}
+
+/// Plays Rock every round.
+#[derive(Debug, Default)]
+pub struct AlwaysRock;
+
+/// Plays every move equally often, chosen at random.
+#[derive(Debug)]
+pub struct UniformRandom {
+    rng: Rng,
+}
+
+/// Plays the move that beats the move the opponent has played most often so far.
+#[derive(Debug, Default)]
+pub struct FrequencyCounter;
+
+/// Predicts the opponent's next move from how often each move followed the opponent's last move so far, and plays
+/// the move that beats it.
+#[derive(Debug, Default)]
+pub struct MarkovPredictor;
+
+/// Plays the player's moves from a strategy guide in order, starting over when the guide runs out.
+#[derive(Debug)]
+pub struct GuideFollower {
+    moves: Vec<Move>,
+}
+
+impl UniformRandom {
+    /// Construct a `UniformRandom` player whose moves are determined by `seed`.
+    pub fn new(seed: u64) -> UniformRandom {
+        UniformRandom {
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl GuideFollower {
+    /// Construct a `GuideFollower` playing the player's moves of the `rounds` of a strategy guide.
+    ///
+    /// Returns `None` if there are no rounds to follow.
+    pub fn new(rounds: &[Round]) -> Option<GuideFollower> {
+        if rounds.is_empty() {
+            return None;
+        }
+        Some(GuideFollower {
+            moves: rounds.iter().map(|round| round.player).collect(),
+        })
+    }
+}
+
+/// Returns the move played most often in `moves`, preferring moves earlier in [`Move::ALL`] on ties.
+///
+/// Returns `None` if there are no moves.
+fn most_frequent(moves: impl Iterator<Item = Move>) -> Option<Move> {
+    let mut counts = [0_usize; 3];
+    for m in moves {
+        counts[m.index()] += 1;
+    }
+    Move::ALL
+        .into_iter()
+        .filter(|m| counts[m.index()] > 0)
+        .fold(None, |most: Option<Move>, m| match most {
+            Some(most) if counts[most.index()] >= counts[m.index()] => Some(most),
+            _ => Some(m),
+        })
+}
+
+impl Player for AlwaysRock {
+    fn name(&self) -> String {
+        "always rock".to_string()
+    }
+
+    fn choose(&mut self, _history: &[Round]) -> Move {
+        Move::Rock
+    }
+}
+
+impl Player for UniformRandom {
+    fn name(&self) -> String {
+        "uniform random".to_string()
+    }
+
+    fn choose(&mut self, _history: &[Round]) -> Move {
+        self.rng.next_move()
+    }
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        most_frequent(history.iter().map(|round| round.opponent))
+            .map_or(Move::Rock, |predicted| predicted.loses())
+    }
+}
+
+impl Player for MarkovPredictor {
+    fn name(&self) -> String {
+        "markov predictor".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        let Some(last) = history.last() else {
+            return Move::Rock;
+        };
+        // Moves the opponent played right after playing the same move as their last one.
+        let followers = history
+            .windows(2)
+            .filter(|pair| pair[0].opponent == last.opponent)
+            .map(|pair| pair[1].opponent);
+
+        most_frequent(followers)
+            .or_else(|| most_frequent(history.iter().map(|round| round.opponent)))
+            .map_or(Move::Rock, |predicted| predicted.loses())
+    }
+}
+
+impl Player for GuideFollower {
+    fn name(&self) -> String {
+        "guide follower".to_string()
+    }
+
+    fn choose(&mut self, history: &[Round]) -> Move {
+        self.moves[history.len() % self.moves.len()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        player::{
+            AlwaysRock, FrequencyCounter, GuideFollower, MarkovPredictor, Player, Rng,
+            UniformRandom,
+        },
+        Move, Round,
+    };
+
+    fn history(opponent: &[Move]) -> Vec<Round> {
+        opponent
+            .iter()
+            .map(|&opponent| Round {
+                player: Move::Rock,
+                opponent,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn random_players_are_seedable() {
+        let moves = |seed| {
+            let mut player = UniformRandom::new(seed);
+            (0..20).map(|_| player.choose(&[])).collect::<Vec<_>>()
+        };
+
+        assert_eq!(moves(7), moves(7));
+        assert_ne!(moves(7), moves(8));
+
+        let mut rng = Rng::new(1);
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[rng.next_move().index()] += 1;
+        }
+        assert!(counts.iter().all(|&count| (900..1100).contains(&count)));
+    }
+
+    #[test]
+    fn bots_choose_moves() {
+        use Move::*;
+
+        assert_eq!(AlwaysRock.choose(&history(&[Paper, Paper])), Rock);
+        assert_eq!(FrequencyCounter.choose(&[]), Rock);
+        assert_eq!(
+            FrequencyCounter.choose(&history(&[Scissors, Paper, Scissors])),
+            Rock
+        );
+        // After Rock the opponent has always played Paper, so Scissors should beat them next.
+        assert_eq!(
+            MarkovPredictor.choose(&history(&[Rock, Paper, Rock, Paper, Scissors, Rock])),
+            Scissors
+        );
+
+        let guide = [
+            Round {
+                player: Paper,
+                opponent: Rock,
+            },
+            Round {
+                player: Scissors,
+                opponent: Rock,
+            },
+        ];
+        let mut follower = GuideFollower::new(&guide).unwrap();
+        assert_eq!(follower.choose(&[]), Paper);
+        assert_eq!(follower.choose(&history(&[Rock, Rock])), Paper);
+        assert_eq!(follower.choose(&history(&[Rock, Rock, Rock])), Scissors);
+        assert!(GuideFollower::new(&[]).is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt::Display;
+
+use crate::{Move, Outcome, Round, Score};
+
+/// Breakdown of the score of every round of a strategy guide.
+#[derive(Debug, PartialEq)]
+pub struct ScoreReport {
+    /// Breakdown of each round, in the order they are played.
+    pub rounds: Vec<RoundBreakdown>,
+    /// Number of rounds won by the player.
+    pub wins: usize,
+    /// Number of rounds that ended in a draw.
+    pub draws: usize,
+    /// Number of rounds lost by the player.
+    pub losses: usize,
+    /// The player's final score.
+    pub total: Score,
+    /// Summary of the rounds where the player chose each move, in the order of [`Move::ALL`].
+    pub moves: Vec<MoveSummary>,
+}
+
+/// The moves, outcome and points scored in a single round.
+#[derive(Debug, PartialEq)]
+pub struct RoundBreakdown {
+    /// Number of the round (starting from 1).
+    pub round: usize,
+    pub opponent: Move,
+    pub player: Move,
+    pub outcome: Outcome,
+    /// Points scored for the player's move.
+    pub move_points: Score,
+    /// Points scored for the outcome of the round.
+    pub outcome_points: Score,
+    /// The player's score after this round.
+    pub running_total: Score,
+}
+
+/// The number of `rounds` where the player chose `player_move` and the points they scored in them.
+#[derive(Debug, PartialEq)]
+pub struct MoveSummary {
+    pub player_move: Move,
+    pub rounds: usize,
+    pub score: Score,
+    /// Fraction of the final score scored in these rounds (0 when nothing was scored at all).
+    pub share: f64,
+}
+
+impl ScoreReport {
+    /// Construct a `ScoreReport` of the `rounds` played.
+    pub fn new(rounds: &[Round]) -> ScoreReport {
+        let mut running_total = 0;
+        let rounds: Vec<RoundBreakdown> = rounds
+            .iter()
+            .enumerate()
+            .map(|(index, round)| {
+                let outcome = round.outcome();
+                running_total += round.score();
+                RoundBreakdown {
+                    round: index + 1,
+                    opponent: round.opponent,
+                    player: round.player,
+                    outcome,
+                    move_points: round.player.score(),
+                    outcome_points: outcome.score(),
+                    running_total,
+                }
+            })
+            .collect();
+
+        let count = |outcome: Outcome| rounds.iter().filter(|r| r.outcome == outcome).count();
+        let total = running_total;
+        let moves = Move::ALL
+            .iter()
+            .map(|&player_move| {
+                let played = rounds.iter().filter(|r| r.player == player_move);
+                let score = played
+                    .clone()
+                    .map(|r| r.move_points + r.outcome_points)
+                    .sum();
+                MoveSummary {
+                    player_move,
+                    rounds: played.count(),
+                    score,
+                    share: if total == 0 {
+                        0.0
+                    } else {
+                        f64::from(score) / f64::from(total)
+                    },
+                }
+            })
+            .collect();
+
+        ScoreReport {
+            wins: count(Outcome::Win),
+            draws: count(Outcome::Draw),
+            losses: count(Outcome::Loss),
+            total,
+            moves,
+            rounds,
+        }
+    }
+
+    /// Returns the breakdown of each round as CSV, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "round,opponent,player,outcome,move_points,outcome_points,running_total\n",
+        );
+        for r in &self.rounds {
+            csv += &format!(
+                "{},{},{},{},{},{},{}\n",
+                r.round,
+                r.opponent,
+                r.player,
+                r.outcome,
+                r.move_points,
+                r.outcome_points,
+                r.running_total
+            );
+        }
+        csv
+    }
+}
+
+impl Display for ScoreReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>5} | {:8} | {:8} | {:7} | {:>4} | {:>7} | {:>7}",
+            "Round", "Opponent", "Player", "Outcome", "Move", "Outcome", "Total"
+        )?;
+        for r in &self.rounds {
+            writeln!(
+                f,
+                "{:>5} | {:8} | {:8} | {:7} | {:>4} | {:>7} | {:>7}",
+                r.round,
+                r.opponent,
+                r.player,
+                r.outcome,
+                r.move_points,
+                r.outcome_points,
+                r.running_total
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{} wins, {} draws, {} losses for a final score of {}",
+            self.wins, self.draws, self.losses, self.total
+        )?;
+        for summary in &self.moves {
+            writeln!(
+                f,
+                "{:8} played {:>5} times, scoring {:>6} ({:5.1}%)",
+                summary.player_move,
+                summary.rounds,
+                summary.score,
+                summary.share * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_moves_strategy_guide, report::ScoreReport, rounds_from_moves_strategy_guide,
+        total_score, Move, Outcome, INPUT_FILE,
+    };
+
+    #[test]
+    fn report_breakdown() {
+        let rounds = parse_moves_strategy_guide("A Y\nB X\nC Z\n").unwrap();
+        let report = ScoreReport::new(&rounds);
+
+        assert_eq!((report.wins, report.draws, report.losses), (1, 1, 1));
+        assert_eq!(report.total, 15);
+        assert_eq!(
+            report
+                .rounds
+                .iter()
+                .map(|r| (r.outcome, r.move_points, r.outcome_points, r.running_total))
+                .collect::<Vec<_>>(),
+            vec![
+                (Outcome::Win, 2, 6, 8),
+                (Outcome::Loss, 1, 0, 9),
+                (Outcome::Draw, 3, 3, 15)
+            ]
+        );
+
+        let scissors = &report.moves[Move::Scissors.index()];
+        assert_eq!((scissors.rounds, scissors.score), (1, 6));
+        assert!((scissors.share - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_csv() {
+        let rounds = parse_moves_strategy_guide("A Y\nB X\n").unwrap();
+
+        assert_eq!(
+            ScoreReport::new(&rounds).to_csv(),
+            "round,opponent,player,outcome,move_points,outcome_points,running_total\n\
+             1,Rock,Paper,Win,2,6,8\n\
+             2,Paper,Rock,Loss,1,0,9\n"
+        );
+    }
+
+    #[test]
+    fn report_matches_total_score() {
+        let rounds = rounds_from_moves_strategy_guide(INPUT_FILE);
+        let report = ScoreReport::new(&rounds);
+
+        assert_eq!(report.total, total_score(&rounds));
+        assert_eq!(
+            report.rounds.len(),
+            report.wins + report.draws + report.losses
+        );
+        assert_eq!(
+            report.moves.iter().map(|m| m.score).sum::<u32>(),
+            report.total
+        );
+    }
+}
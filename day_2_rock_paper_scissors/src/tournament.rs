@@ -0,0 +1,176 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Display,
+};
+
+use crate::{Player, Round, TournamentScore};
+
+/// A player's results over all of the games they played in a tournament.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    /// Total score of every round the player played.
+    pub score: TournamentScore,
+    /// Games where the player scored more than their opponent.
+    pub wins: usize,
+    /// Games where the player scored the same as their opponent.
+    pub draws: usize,
+    /// Games where the player scored less than their opponent.
+    pub losses: usize,
+}
+
+/// Standings of every player in a tournament, sorted by descending total score.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard(pub Vec<Standing>);
+
+/// Returns the scores of both players after playing a game of `rounds` rounds against each other.
+fn play_game(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    rounds: usize,
+) -> (TournamentScore, TournamentScore) {
+    let mut first_history: Vec<Round> = Vec::with_capacity(rounds);
+    let mut second_history: Vec<Round> = Vec::with_capacity(rounds);
+
+    for _ in 0..rounds {
+        let first_move = first.choose(&first_history);
+        let second_move = second.choose(&second_history);
+        first_history.push(Round {
+            player: first_move,
+            opponent: second_move,
+        });
+        second_history.push(Round {
+            player: second_move,
+            opponent: first_move,
+        });
+    }
+
+    (
+        first_history
+            .iter()
+            .map(|round| TournamentScore::from(round.score()))
+            .sum(),
+        second_history
+            .iter()
+            .map(|round| TournamentScore::from(round.score()))
+            .sum(),
+    )
+}
+
+/// Returns the leaderboard of a round-robin tournament where every pair of `players` plays `games` games of
+/// `rounds` rounds against each other.
+///
+/// Players keep their state between games (such as a random player's generator), but only see the history of the
+/// game they are currently playing.
+pub fn round_robin(players: &mut [Box<dyn Player>], games: usize, rounds: usize) -> Leaderboard {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name(),
+            score: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        })
+        .collect();
+
+    for second in 1..players.len() {
+        let (before, after) = players.split_at_mut(second);
+        for (first, first_player) in before.iter_mut().enumerate() {
+            for _ in 0..games {
+                let (first_score, second_score) =
+                    play_game(first_player.as_mut(), after[0].as_mut(), rounds);
+
+                standings[first].score += first_score;
+                standings[second].score += second_score;
+                match first_score.cmp(&second_score) {
+                    Ordering::Greater => {
+                        standings[first].wins += 1;
+                        standings[second].losses += 1;
+                    }
+                    Ordering::Equal => {
+                        standings[first].draws += 1;
+                        standings[second].draws += 1;
+                    }
+                    Ordering::Less => {
+                        standings[first].losses += 1;
+                        standings[second].wins += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // Sort by descending score, keeping players with equal scores in the order they were given.
+    standings.sort_by_key(|standing| Reverse(standing.score));
+    Leaderboard(standings)
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>4} | {:20} | {:>10} | {:>5} | {:>5} | {:>6}",
+            "Rank", "Player", "Score", "Wins", "Draws", "Losses"
+        )?;
+        for (rank, standing) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4} | {:20} | {:>10} | {:>5} | {:>5} | {:>6}",
+                rank + 1,
+                standing.name,
+                standing.score,
+                standing.wins,
+                standing.draws,
+                standing.losses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        player::{AlwaysRock, FrequencyCounter, MarkovPredictor, Player, UniformRandom},
+        tournament::round_robin,
+    };
+
+    #[test]
+    fn predictors_beat_always_rock() {
+        let mut players: Vec<Box<dyn Player>> = vec![
+            Box::new(AlwaysRock),
+            Box::new(FrequencyCounter),
+            Box::new(MarkovPredictor),
+        ];
+
+        let leaderboard = round_robin(&mut players, 3, 100);
+
+        let rock = leaderboard
+            .0
+            .iter()
+            .find(|standing| standing.name == "always rock")
+            .unwrap();
+        assert_eq!((rock.wins, rock.losses), (0, 6));
+        assert_eq!(leaderboard.0.last().unwrap().name, "always rock");
+        // Every player plays 2 opponents 3 times each.
+        assert!(leaderboard
+            .0
+            .iter()
+            .all(|standing| standing.wins + standing.draws + standing.losses == 6));
+    }
+
+    #[test]
+    fn tournaments_are_reproducible() {
+        let tournament = || {
+            let mut players: Vec<Box<dyn Player>> = vec![
+                Box::new(UniformRandom::new(1)),
+                Box::new(UniformRandom::new(2)),
+                Box::new(MarkovPredictor),
+            ];
+            round_robin(&mut players, 5, 50)
+        };
+
+        assert_eq!(tournament(), tournament());
+    }
+}
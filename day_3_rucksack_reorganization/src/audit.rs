@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use crate::{Group, Rucksack, Shared};
+
+/// What was checked by an [`Audit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subject {
+    /// Each rucksack should have exactly one item in both of its compartments.
+    Rucksack,
+    /// Each group should have exactly one item in all of its rucksacks.
+    Group,
+}
+
+/// A rucksack or group that doesn't have exactly one shared item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Position (from 0) of the rucksack or group in the list that was audited.
+    pub index: usize,
+    pub shared: Shared,
+}
+
+/// Every rucksack or group violating the rule that it has exactly one shared item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Audit {
+    pub subject: Subject,
+    /// Number of rucksacks or groups that were checked.
+    pub checked: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl Audit {
+    /// Returns the audit of the `shared` items of each rucksack or group.
+    fn new(subject: Subject, shared: impl Iterator<Item = Shared>) -> Audit {
+        let mut checked = 0;
+        let mut violations = Vec::new();
+        for (index, shared) in shared.enumerate() {
+            checked += 1;
+            if !matches!(shared, Shared::One(_)) {
+                violations.push(Violation { index, shared });
+            }
+        }
+        Audit {
+            subject,
+            checked,
+            violations,
+        }
+    }
+
+    /// Returns true if every rucksack or group has exactly one shared item.
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Returns the audit of every rucksack's duplicate items (see [`Rucksack::duplicate_item`]).
+pub fn audit_rucksacks(rucksacks: &[Rucksack]) -> Audit {
+    Audit::new(
+        Subject::Rucksack,
+        rucksacks.iter().map(Rucksack::duplicate_item),
+    )
+}
+
+/// Returns the audit of every group's common items (see [`Group::common_item`]).
+pub fn audit_groups(groups: &[Group]) -> Audit {
+    Audit::new(Subject::Group, groups.iter().map(Group::common_item))
+}
+
+impl Display for Subject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subject::Rucksack => f.pad("rucksack"),
+            Subject::Group => f.pad("group"),
+        }
+    }
+}
+
+impl Display for Audit {
+    /// Lists each violation, numbering rucksacks and groups from 1 (so rucksacks are numbered by their line).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} of {} {}s don't have exactly one shared item",
+            self.violations.len(),
+            self.checked,
+            self.subject
+        )?;
+        for violation in &self.violations {
+            writeln!(
+                f,
+                "{} {}: {}",
+                self.subject,
+                violation.index + 1,
+                violation.shared
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        audit::{audit_groups, audit_rucksacks},
+        Group, Item, Rucksack, Shared,
+    };
+
+    fn rucksack(items: &str) -> Rucksack {
+        Rucksack {
+            items: items.to_string(),
+        }
+    }
+
+    #[test]
+    fn audit_reports_every_violation() {
+        let rucksacks = vec![rucksack("abcb"), rucksack("abcd"), rucksack("abba")];
+
+        let audit = audit_rucksacks(&rucksacks);
+
+        assert!(!audit.passed());
+        assert_eq!(audit.checked, 3);
+        let indexes: Vec<usize> = audit.violations.iter().map(|v| v.index).collect();
+        assert_eq!(indexes, vec![1, 2]);
+        assert_eq!(audit.violations[0].shared, Shared::None);
+        assert_eq!(
+            audit.violations[1].shared.items(),
+            vec![Item('a'), Item('b')]
+        );
+        assert_eq!(
+            audit.to_string(),
+            "2 of 3 rucksacks don't have exactly one shared item\n\
+             rucksack 2: no shared items\n\
+             rucksack 3: 2 shared items: a (at 0; at 1), b (at 1; at 0)\n"
+        );
+
+        assert!(!audit_groups(&[Group::new(rucksacks)]).passed());
+        assert!(audit_groups(&[Group::new(vec![rucksack("abcA"), rucksack("Azzz")])]).passed());
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::{btree_set, BTreeSet},
+    ops::{BitAnd, BitOr, Sub},
+};
+
+use crate::{AocPriorities, Item, Priority, PriorityScheme};
+
+/// Set of item types.
+///
+/// The 52 letters of the puzzle's items are stored as a bitmask with the bit at each item's priority (see
+/// [`AocPriorities`]) set, so set operations on them are single bitwise operations on a `u64`. Items of other
+/// [`PriorityScheme`]s (such as digits or other Unicode letters) are stored in a `BTreeSet`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet {
+    letters: u64,
+    others: BTreeSet<char>,
+}
+
+impl ItemSet {
+    /// The set without any items.
+    pub const EMPTY: ItemSet = ItemSet {
+        letters: 0,
+        others: BTreeSet::new(),
+    };
+
+    /// Construct the `ItemSet` of every char in `items`.
+    pub fn from_items(items: &str) -> ItemSet {
+        items.chars().map(Item).collect()
+    }
+
+    /// Returns the bitmask with only the bit of the `item` set, or `None` if it isn't one of the puzzle's letters.
+    fn bit(item: &Item) -> Option<u64> {
+        AocPriorities.priority(item.0).map(|priority| 1 << priority)
+    }
+
+    /// Add the `item` to the set.
+    pub fn insert(&mut self, item: &Item) {
+        match ItemSet::bit(item) {
+            Some(bit) => self.letters |= bit,
+            None => {
+                self.others.insert(item.0);
+            }
+        }
+    }
+
+    /// Returns true if the `item` is in the set.
+    pub fn contains(&self, item: &Item) -> bool {
+        match ItemSet::bit(item) {
+            Some(bit) => self.letters & bit != 0,
+            None => self.others.contains(&item.0),
+        }
+    }
+
+    /// Returns the set of items in either set.
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet {
+            letters: self.letters | other.letters,
+            others: &self.others | &other.others,
+        }
+    }
+
+    /// Returns the set of items in both sets.
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet {
+            letters: self.letters & other.letters,
+            others: &self.others & &other.others,
+        }
+    }
+
+    /// Returns the set of items in this set but not the `other` set.
+    pub fn difference(&self, other: &ItemSet) -> ItemSet {
+        ItemSet {
+            letters: self.letters & !other.letters,
+            others: &self.others - &other.others,
+        }
+    }
+
+    /// Returns the number of items in the set.
+    pub fn len(&self) -> usize {
+        self.letters.count_ones() as usize + self.others.len()
+    }
+
+    /// Returns true if there are no items in the set.
+    pub fn is_empty(&self) -> bool {
+        self.letters == 0 && self.others.is_empty()
+    }
+
+    /// Returns an iterator over the items in the set: the puzzle's letters in order of increasing priority, then any
+    /// other items in order of their chars.
+    pub fn iter(&self) -> Items<'_> {
+        Items {
+            letters: self.letters,
+            others: self.others.iter(),
+        }
+    }
+}
+
+/// Iterator over the items of an [`ItemSet`] (see [`ItemSet::iter`]).
+#[derive(Clone, Debug)]
+pub struct Items<'a> {
+    letters: u64,
+    others: btree_set::Iter<'a, char>,
+}
+
+impl Iterator for Items<'_> {
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.letters == 0 {
+            return self.others.next().map(|&c| Item(c));
+        }
+        let priority = self.letters.trailing_zeros() as Priority;
+        // Clear the lowest set bit.
+        self.letters &= self.letters - 1;
+        Some(Item::from_priority(priority))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.letters.count_ones() as usize + self.others.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> IntoIterator for &'a ItemSet {
+    type Item = Item;
+    type IntoIter = Items<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Item> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = Item>>(items: T) -> Self {
+        let mut set = ItemSet::EMPTY;
+        for item in items {
+            set.insert(&item);
+        }
+        set
+    }
+}
+
+impl BitOr for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: &ItemSet) -> ItemSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: &ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for &ItemSet {
+    type Output = ItemSet;
+
+    fn sub(self, other: &ItemSet) -> ItemSet {
+        self.difference(other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{item_set::ItemSet, Item};
+
+    #[test]
+    fn set_operations() {
+        let a = ItemSet::from_items("abcZ");
+        let b = ItemSet::from_items("cdZZ");
+
+        assert_eq!(&a | &b, ItemSet::from_items("abcdZ"));
+        assert_eq!(&a & &b, ItemSet::from_items("cZ"));
+        assert_eq!(&a - &b, ItemSet::from_items("ab"));
+        assert_eq!((&a & &b).len(), 2);
+        assert!(a.contains(&Item('Z')));
+        assert!(!a.contains(&Item('A')));
+        assert!((&a - &a).is_empty());
+
+        let c = ItemSet::from_items("aé9");
+        let d = ItemSet::from_items("9éb");
+        assert_eq!(&c & &d, ItemSet::from_items("é9"));
+        assert_eq!((&c | &d).len(), 4);
+        assert!((&c - &d).contains(&Item('a')));
+        assert!(!(&c - &d).contains(&Item('é')));
+    }
+
+    #[test]
+    fn iterates_by_priority() {
+        let items: String = ItemSet::from_items("ZaAzb")
+            .iter()
+            .map(|item| item.0)
+            .collect();
+
+        assert_eq!(items, "abzAZ");
+        let items: String = ItemSet::from_items("éZ1a")
+            .iter()
+            .map(|item| item.0)
+            .collect();
+        assert_eq!(items, "aZ1é");
+        assert_eq!(ItemSet::EMPTY.iter().next(), None);
+    }
+}
@@ -0,0 +1,405 @@
+use std::{fs, num::NonZeroUsize};
+
+pub use audit::{audit_groups, audit_rucksacks, Audit, Subject, Violation};
+pub use item_set::{ItemSet, Items};
+pub use parser::{
+    parse_groups, parse_groups_with_grouping, parse_groups_with_scheme, parse_keyed_rucksacks,
+    parse_keyed_rucksacks_with_scheme, parse_rucksacks, parse_rucksacks_with_scheme, RucksackError,
+    RucksackErrorKind,
+};
+pub use priority::{AocPriorities, PriorityScheme, PriorityTable, PriorityTableError, UnknownItem};
+pub use repack::{plan_repack, plan_repacks, RepackPlan, Swap};
+pub use shared::{Shared, SharedItem};
+
+mod audit;
+mod item_set;
+mod parser;
+mod priority;
+mod repack;
+mod shared;
+
+pub static INPUT_FILE: &str = "inputs/day3.txt";
+
+/// Group of elves with rucksacks that share common items.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    /// Key shared by the group's rucksacks, if they were grouped by [`Grouping::KeyColumn`].
+    pub key: Option<String>,
+    pub rucksacks: Vec<Rucksack>,
+}
+
+/// How the rucksacks on the lines of the input are split into groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// Consecutive rucksacks in groups of this size. The number of rucksacks must be a multiple of it.
+    Chunks(NonZeroUsize),
+    /// Every run of this many consecutive rucksacks, so most rucksacks are in several groups.
+    Windows(NonZeroUsize),
+    /// Rucksacks with the same key in the first column of their line (e.g. `team-1 vJrwpWtwJgWrhcsFMMfFFhFp`), in
+    /// order of each key's first appearance.
+    KeyColumn,
+}
+
+/// Type of item, constructed from a char that is an item in a [`PriorityScheme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Item(char);
+
+pub type Priority = u32;
+
+/// Total priority of many items, wide enough that summing even huge priorities from a [`PriorityTable`] doesn't
+/// overflow in practice.
+pub type TotalPriority = u64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rucksack {
+    pub items: String,
+}
+
+impl Group {
+    /// Construct a `Group` of `rucksacks` without a key.
+    pub fn new(rucksacks: Vec<Rucksack>) -> Group {
+        Group {
+            key: None,
+            rucksacks,
+        }
+    }
+
+    /// Returns the item expected to be found in all of the group's rucksacks, or else every item found in all of
+    /// them (or none), with their positions in each rucksack.
+    ///
+    /// A group without rucksacks has no common items.
+    pub fn common_item(&self) -> Shared {
+        let common = self
+            .rucksacks
+            .iter()
+            .map(Rucksack::item_set)
+            .reduce(|common, items| &common & &items)
+            .unwrap_or(ItemSet::EMPTY);
+        let rucksacks: Vec<&str> = self.rucksacks.iter().map(|r| r.items.as_str()).collect();
+        Shared::new(common, &rucksacks)
+    }
+}
+
+impl Default for Grouping {
+    /// Groups of three consecutive rucksacks, as in the puzzle.
+    fn default() -> Self {
+        Grouping::Chunks(NonZeroUsize::new(3).expect("3 is not zero"))
+    }
+}
+
+impl Item {
+    /// Construct the `Item` of the char `c`, which must be an item in the puzzle's scheme ([`AocPriorities`]).
+    pub fn new(c: char) -> Result<Item, UnknownItem> {
+        Item::with_scheme(c, &AocPriorities)
+    }
+
+    /// Construct the `Item` of the char `c`, which must be an item in the `scheme`.
+    pub fn with_scheme(c: char, scheme: &dyn PriorityScheme) -> Result<Item, UnknownItem> {
+        match scheme.priority(c) {
+            Some(_) => Ok(Item(c)),
+            None => Err(UnknownItem(c)),
+        }
+    }
+
+    /// Returns the char of the item.
+    pub fn char(&self) -> char {
+        self.0
+    }
+
+    /// Returns the priority of the item in the puzzle's scheme ([`AocPriorities`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item isn't in the puzzle's scheme; use [`Item::priority_in`] for items of other schemes.
+    pub fn priority(&self) -> Priority {
+        self.priority_in(&AocPriorities)
+            .unwrap_or_else(|| panic!("Item {:?} is not a letter from a to z or A to Z", self.0))
+    }
+
+    /// Returns the priority of the item in the `scheme`, or `None` if it isn't an item in the `scheme`.
+    pub fn priority_in(&self, scheme: &dyn PriorityScheme) -> Option<Priority> {
+        scheme.priority(self.0)
+    }
+
+    /// Returns the item with the `priority` (from 1 to 52) in the puzzle's scheme ([`AocPriorities`]).
+    pub fn from_priority(priority: Priority) -> Item {
+        let c = match priority {
+            1..=26 => b'a' + (priority - 1) as u8,
+            27..=52 => b'A' + (priority - 27) as u8,
+            _ => panic!("Priority {priority} is not of a letter from a to z or A to Z"),
+        };
+        Item(c as char)
+    }
+}
+
+impl Rucksack {
+    /// Returns the byte index where the right compartment starts, after half of the items.
+    fn compartment_boundary(&self) -> usize {
+        let compartment_items = self.items.chars().count() / 2;
+        self.items
+            .char_indices()
+            .nth(compartment_items)
+            .map_or(self.items.len(), |(index, _)| index)
+    }
+
+    pub fn left_compartment(&self) -> &str {
+        &self.items[..self.compartment_boundary()]
+    }
+
+    pub fn right_compartment(&self) -> &str {
+        &self.items[self.compartment_boundary()..]
+    }
+
+    /// Swap the item at position `left` (from 0) in the left compartment with the item at position `right` in the
+    /// right compartment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either position is outside of its compartment.
+    pub fn swap_items(&mut self, left: usize, right: usize) {
+        let mut items: Vec<char> = self.items.chars().collect();
+        let compartment_items = items.len() / 2;
+        assert!(
+            left < compartment_items && right < items.len() - compartment_items,
+            "Positions {left} and {right} are not in compartments of {compartment_items} items"
+        );
+        items.swap(left, compartment_items + right);
+        self.items = items.into_iter().collect();
+    }
+
+    /// Returns the set of all items in the rucksack.
+    pub fn item_set(&self) -> ItemSet {
+        ItemSet::from_items(&self.items)
+    }
+
+    /// Returns the set of items found in both compartments of the rucksack.
+    pub fn duplicate_items(&self) -> ItemSet {
+        &ItemSet::from_items(self.left_compartment())
+            & &ItemSet::from_items(self.right_compartment())
+    }
+
+    /// Returns the item expected to be found in both compartments, or else every item found in both of them (or
+    /// none), with their positions in each compartment.
+    pub fn duplicate_item(&self) -> Shared {
+        Shared::new(
+            self.duplicate_items(),
+            &[self.left_compartment(), self.right_compartment()],
+        )
+    }
+}
+
+/// Returns the sum of the priorities of the common items found in each group's rucksacks.
+pub fn groups_common_items_priority(groups: &[Group]) -> TotalPriority {
+    groups_common_items_priority_with_scheme(groups, &AocPriorities)
+}
+
+/// Returns the sum of the priorities in the `scheme` of the common items found in each group's rucksacks.
+///
+/// Items that aren't in the `scheme` have no priority, so they don't add to the sum.
+pub fn groups_common_items_priority_with_scheme(
+    groups: &[Group],
+    scheme: &dyn PriorityScheme,
+) -> TotalPriority {
+    groups
+        .iter()
+        .map(|g| {
+            g.common_item()
+                .items()
+                .iter()
+                .filter_map(|item| item.priority_in(scheme))
+                .map(TotalPriority::from)
+                .sum::<TotalPriority>()
+        })
+        .sum::<TotalPriority>()
+}
+
+/// Returns the sum of the priorities of duplicate items that appear in both compartments of `rucksacks`.
+pub fn rucksacks_duplicate_items_priority(rucksacks: &[Rucksack]) -> TotalPriority {
+    rucksacks_duplicate_items_priority_with_scheme(rucksacks, &AocPriorities)
+}
+
+/// Returns the sum of the priorities in the `scheme` of duplicate items that appear in both compartments of
+/// `rucksacks`.
+///
+/// Items that aren't in the `scheme` have no priority, so they don't add to the sum.
+pub fn rucksacks_duplicate_items_priority_with_scheme(
+    rucksacks: &[Rucksack],
+    scheme: &dyn PriorityScheme,
+) -> TotalPriority {
+    rucksacks
+        .iter()
+        .map(|r| {
+            r.duplicate_items()
+                .iter()
+                .filter_map(|item| item.priority_in(scheme))
+                .map(TotalPriority::from)
+                .sum::<TotalPriority>()
+        })
+        .sum::<TotalPriority>()
+}
+
+/// Returns the sum of the priorities of the common item found in the groups' rucksacks.
+///
+/// This solves Day 3 Part 2.
+pub fn group_common_items_priority(input_file: &str) -> TotalPriority {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    let groups = parse_groups(&input).unwrap_or_else(|e| panic!("Failed to parse input file\n{e}"));
+    groups_common_items_priority(&groups)
+}
+
+/// Returns the sum of the priorities of duplicate items that appear in both compartments in Rucksacks.
+///
+/// This solves Day 3 Part 1.
+pub fn duplicate_items_priority(input_file: &str) -> TotalPriority {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    let rucksacks =
+        parse_rucksacks(&input).unwrap_or_else(|e| panic!("Failed to parse input file\n{e}"));
+    rucksacks_duplicate_items_priority(&rucksacks)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        duplicate_items_priority, group_common_items_priority,
+        groups_common_items_priority_with_scheme, rucksacks_duplicate_items_priority_with_scheme,
+        Group, Item, PriorityTable, Rucksack, Shared, SharedItem, UnknownItem, INPUT_FILE,
+    };
+
+    #[test]
+    fn solve_day_3_part_1() {
+        assert_eq!(duplicate_items_priority(INPUT_FILE), 7831);
+    }
+
+    #[test]
+    fn solve_day_3_part_2() {
+        assert_eq!(group_common_items_priority(INPUT_FILE), 2683);
+    }
+
+    #[test]
+    fn left_right_rucksack_compartments() {
+        let rucksack = Rucksack {
+            items: "helloworld".to_string(),
+        };
+
+        assert_eq!(rucksack.left_compartment(), "hello");
+        assert_eq!(rucksack.right_compartment(), "world");
+    }
+
+    #[test]
+    fn swap_items() {
+        let mut rucksack = Rucksack {
+            items: "helloworld".to_string(),
+        };
+
+        // Positions are counted from the start of each compartment.
+        rucksack.swap_items(0, 4);
+        assert_eq!(rucksack.left_compartment(), "dello");
+        assert_eq!(rucksack.right_compartment(), "worlh");
+        rucksack.swap_items(4, 0);
+        assert_eq!(rucksack.items, "dellwoorlh");
+        rucksack.swap_items(4, 0);
+        rucksack.swap_items(0, 4);
+        assert_eq!(rucksack.items, "helloworld");
+
+        let mut rucksack = Rucksack {
+            items: "aβγδ".to_string(),
+        };
+        rucksack.swap_items(1, 1);
+        assert_eq!(rucksack.items, "aδγβ");
+    }
+
+    #[test]
+    #[should_panic(expected = "are not in compartments of 5 items")]
+    fn swap_items_outside_compartments() {
+        let mut rucksack = Rucksack {
+            items: "helloworld".to_string(),
+        };
+
+        rucksack.swap_items(5, 0);
+    }
+
+    #[test]
+    fn duplicate_and_common_items() {
+        let rucksack = |items: &str| Rucksack {
+            items: items.to_string(),
+        };
+
+        assert_eq!(
+            rucksack("vJrwpWtwJgWrhcsFMMfFFhFp").duplicate_item(),
+            Shared::One(SharedItem {
+                item: Item('p'),
+                positions: vec![vec![4], vec![11]]
+            })
+        );
+        assert_eq!(rucksack("abcd").duplicate_item(), Shared::None);
+        assert_eq!(
+            rucksack("abcbac").duplicate_item().items(),
+            vec![Item('a'), Item('b'), Item('c')]
+        );
+        let group = Group::new(vec![
+            rucksack("vJrwpWtwJgWrhcsFMMfFFhFp"),
+            rucksack("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"),
+            rucksack("PmmdzqPrVvPwwTWBwg"),
+        ]);
+        assert_eq!(group.common_item().item(), Some(Item('r')));
+
+        let pair = Group::new(vec![rucksack("abcD"), rucksack("DcEa")]);
+        assert_eq!(
+            pair.common_item().items(),
+            vec![Item('a'), Item('c'), Item('D')]
+        );
+        assert_eq!(
+            Group::new(vec![rucksack("ab"), rucksack("cd")]).common_item(),
+            Shared::None
+        );
+        assert_eq!(Group::new(Vec::new()).common_item(), Shared::None);
+    }
+
+    #[test]
+    fn total_priorities_beyond_item_priorities() {
+        let scheme: PriorityTable = "a=4294967295\nb=4294967295\nc=4294967295".parse().unwrap();
+        let rucksacks = [
+            Rucksack {
+                items: "abab".to_string(),
+            },
+            Rucksack {
+                items: "cc".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            rucksacks_duplicate_items_priority_with_scheme(&rucksacks, &scheme),
+            3 * 4294967295
+        );
+        let group = Group::new(vec![rucksacks[0].clone(), rucksacks[0].clone()]);
+        assert_eq!(
+            groups_common_items_priority_with_scheme(&[group.clone(), group], &scheme),
+            4 * 4294967295
+        );
+    }
+
+    #[test]
+    fn priorities() {
+        assert_eq!(Item('a').priority(), 1);
+        assert_eq!(Item('b').priority(), 2);
+        assert_eq!(Item('z').priority(), 26);
+        assert_eq!(Item('A').priority(), 27);
+        assert_eq!(Item('Z').priority(), 52);
+
+        for priority in 1..=52 {
+            assert_eq!(Item::from_priority(priority).priority(), priority);
+        }
+
+        assert_eq!(Item::new('q'), Ok(Item('q')));
+        assert_eq!(Item::new('1'), Err(UnknownItem('1')));
+        assert_eq!(Item::new('é'), Err(UnknownItem('é')));
+        let scheme: PriorityTable = "é=1000".parse().unwrap();
+        assert_eq!(
+            Item::with_scheme('é', &scheme)
+                .unwrap()
+                .priority_in(&scheme),
+            Some(1000)
+        );
+        assert_eq!(Item('a').priority_in(&scheme), None);
+    }
+}
@@ -0,0 +1,435 @@
+#![allow(clippy::result_large_err)]
+
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use pear::{
+    error::Expected,
+    input::{Input as _, Pear, Show, Span, Text},
+    macros::{parse_error, parser},
+    parsers::*,
+};
+
+use parse_error::{end_of_lines, skip_carriage_return, ParseError};
+
+use crate::{AocPriorities, Group, Grouping, PriorityScheme, Rucksack};
+
+/// Use `Text` as the `Input`; this parses `char` tokens.
+type Input<'a> = Pear<Text<'a>>;
+
+/// Parser results carry a [`RucksackErrorKind`] rather than `pear`'s own error so the kind of problem isn't lost.
+///
+/// See [`pear::macros::parser`] for details.
+type Result<'a, T> = std::result::Result<T, pear::error::ParseError<Span<'a>, RucksackErrorKind>>;
+
+/// The kind of problem found when parsing rucksacks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RucksackErrorKind {
+    /// A rucksack has an odd number of items, so it can't be split into two equal compartments.
+    OddItemCount(usize),
+    /// A rucksack has a char that isn't an item in the priority scheme.
+    InvalidItem(char),
+    /// A line has no items.
+    EmptyLine,
+    /// A line has no key before its items, when grouping by [`Grouping::KeyColumn`].
+    MissingKey,
+    /// The last group has fewer rucksacks than the group size.
+    PartialGroup { size: usize, rucksacks: usize },
+    /// Any other problem reported by the parser.
+    Expected(String),
+}
+
+/// Error produced when parsing rucksacks fails, with the location in the input where the problem was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RucksackError {
+    pub kind: RucksackErrorKind,
+    pub location: ParseError,
+}
+
+impl Display for RucksackErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RucksackErrorKind::OddItemCount(items) => write!(
+                f,
+                "expected an even number of items, but the rucksack has {items}"
+            ),
+            RucksackErrorKind::InvalidItem(c) => {
+                write!(f, "expected an item in the priority scheme but found {c:?}")
+            }
+            RucksackErrorKind::EmptyLine => {
+                write!(f, "expected the items of a rucksack but found none")
+            }
+            RucksackErrorKind::MissingKey => {
+                write!(f, "expected a key before the rucksack's items")
+            }
+            RucksackErrorKind::PartialGroup { size, rucksacks } => write!(
+                f,
+                "expected a group of {size} rucksacks, but the last group has {rucksacks}"
+            ),
+            RucksackErrorKind::Expected(expected) => write!(f, "{expected}"),
+        }
+    }
+}
+
+impl<T: Show, S: Show> From<Expected<T, S>> for RucksackErrorKind {
+    fn from(expected: Expected<T, S>) -> Self {
+        RucksackErrorKind::Expected(expected.to_string())
+    }
+}
+
+impl Display for RucksackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.location)
+    }
+}
+
+impl Error for RucksackError {}
+
+/// Returns true if the char separates a key from the items of a rucksack
+#[inline]
+fn is_separator_char(&c: &char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Parses the items of a rucksack on a line, and the end of the line.
+#[parser]
+fn rucksack<'a>(input: &mut Input<'a>, scheme: &dyn PriorityScheme) -> Result<'a, Rucksack> {
+    let items = take_while(|&c: &char| scheme.priority(c).is_some())?;
+    skip_carriage_return(input);
+    match input.token() {
+        Some('\n') | None => {}
+        Some(c) => parse_error!(RucksackErrorKind::InvalidItem(c))?,
+    }
+    if items.is_empty() {
+        parse_error!(RucksackErrorKind::EmptyLine)?;
+    }
+    let item_count = items.chars().count();
+    if item_count % 2 == 1 {
+        parse_error!(RucksackErrorKind::OddItemCount(item_count))?;
+    }
+    if input.has(1) {
+        eat('\n')?;
+    }
+    Rucksack {
+        items: items.to_string(),
+    }
+}
+
+/// Parses a rucksack from each line, ignoring any empty lines at the end of the input.
+///
+/// `pear`'s `collect` only accepts parsers with its own error type, so the lines are collected here instead.
+#[parser]
+fn rucksacks<'a>(input: &mut Input<'a>, scheme: &dyn PriorityScheme) -> Result<'a, Vec<Rucksack>> {
+    let mut rucksacks: Vec<Rucksack> = Vec::new();
+    while !end_of_lines(input) {
+        rucksacks.push(rucksack(scheme)?);
+    }
+    rucksacks
+}
+
+/// Parses a key, whitespace, and the items of a rucksack on a line, and the end of the line.
+#[parser]
+fn keyed_rucksack<'a>(
+    input: &mut Input<'a>,
+    scheme: &dyn PriorityScheme,
+) -> Result<'a, (String, Rucksack)> {
+    let key = take_while(|c: &char| !c.is_whitespace())?;
+    if key.is_empty() {
+        match input.token() {
+            Some('\r' | '\n') | None => parse_error!(RucksackErrorKind::EmptyLine)?,
+            Some(_) => parse_error!(RucksackErrorKind::MissingKey)?,
+        }
+    }
+    skip_while(is_separator_char)?;
+    (key.to_string(), rucksack(scheme)?)
+}
+
+/// Parses a key and a rucksack from each line, ignoring any empty lines at the end of the input.
+#[parser]
+fn keyed_rucksacks<'a>(
+    input: &mut Input<'a>,
+    scheme: &dyn PriorityScheme,
+) -> Result<'a, Vec<(String, Rucksack)>> {
+    let mut rucksacks: Vec<(String, Rucksack)> = Vec::new();
+    while !end_of_lines(input) {
+        rucksacks.push(keyed_rucksack(scheme)?);
+    }
+    rucksacks
+}
+
+/// Returns the [`RucksackError`] locating the `error` in the `input`.
+fn rucksack_error(
+    input: &str,
+    error: pear::error::ParseError<Span<'_>, RucksackErrorKind>,
+) -> RucksackError {
+    RucksackError {
+        location: ParseError::from_pear(input, &error),
+        kind: error.error,
+    }
+}
+
+/// Parse a rucksack from each line of `input`, with the items of the puzzle's scheme ([`AocPriorities`]).
+///
+/// Each line must hold an even number of items, and nothing else.
+pub fn parse_rucksacks(input: &str) -> std::result::Result<Vec<Rucksack>, RucksackError> {
+    parse_rucksacks_with_scheme(input, &AocPriorities)
+}
+
+/// Parse a rucksack from each line of `input`, with the items of the `scheme`.
+///
+/// Each line must hold an even number of items, and nothing else.
+pub fn parse_rucksacks_with_scheme(
+    input: &str,
+    scheme: &dyn PriorityScheme,
+) -> std::result::Result<Vec<Rucksack>, RucksackError> {
+    let mut text = Input::new(input);
+    rucksacks(&mut text, scheme).map_err(|e| rucksack_error(input, e))
+}
+
+/// Parse a key and a rucksack from each line of `input`, where the key is the first column of the line (e.g.
+/// `team-1 vJrwpWtwJgWrhcsFMMfFFhFp`).
+///
+/// Keys are any chars other than whitespace, and are separated from the items by spaces or tabs. Items are those of
+/// the puzzle's scheme ([`AocPriorities`]).
+pub fn parse_keyed_rucksacks(
+    input: &str,
+) -> std::result::Result<Vec<(String, Rucksack)>, RucksackError> {
+    parse_keyed_rucksacks_with_scheme(input, &AocPriorities)
+}
+
+/// Parse a key and a rucksack from each line of `input` (see [`parse_keyed_rucksacks`]), with the items of the
+/// `scheme`.
+pub fn parse_keyed_rucksacks_with_scheme(
+    input: &str,
+    scheme: &dyn PriorityScheme,
+) -> std::result::Result<Vec<(String, Rucksack)>, RucksackError> {
+    let mut text = Input::new(input);
+    keyed_rucksacks(&mut text, scheme).map_err(|e| rucksack_error(input, e))
+}
+
+/// Parse groups of three consecutive rucksacks from the lines of `input`.
+///
+/// Every rucksack must be valid (see [`parse_rucksacks`]) and the number of rucksacks must be a multiple of 3.
+pub fn parse_groups(input: &str) -> std::result::Result<Vec<Group>, RucksackError> {
+    parse_groups_with_grouping(input, Grouping::default())
+}
+
+/// Parse groups of rucksacks from the lines of `input`, split into groups by `grouping`.
+///
+/// Every rucksack must be valid (see [`parse_rucksacks`] and [`parse_keyed_rucksacks`]).
+pub fn parse_groups_with_grouping(
+    input: &str,
+    grouping: Grouping,
+) -> std::result::Result<Vec<Group>, RucksackError> {
+    parse_groups_with_scheme(input, grouping, &AocPriorities)
+}
+
+/// Parse groups of rucksacks from the lines of `input` (see [`parse_groups_with_grouping`]), with the items of the
+/// `scheme`.
+pub fn parse_groups_with_scheme(
+    input: &str,
+    grouping: Grouping,
+    scheme: &dyn PriorityScheme,
+) -> std::result::Result<Vec<Group>, RucksackError> {
+    match grouping {
+        Grouping::Chunks(size) => {
+            let size = size.get();
+            let rucksacks = parse_rucksacks_with_scheme(input, scheme)?;
+
+            let partial_group = rucksacks.len() % size;
+            if partial_group != 0 {
+                // Locate the error at the first line of the partial group.
+                let line = rucksacks.len() - partial_group;
+                let offset = input.split_inclusive('\n').take(line).map(str::len).sum();
+                let kind = RucksackErrorKind::PartialGroup {
+                    size,
+                    rucksacks: partial_group,
+                };
+                return Err(RucksackError {
+                    location: ParseError::new(input, offset, kind.to_string()),
+                    kind,
+                });
+            }
+
+            Ok(rucksacks
+                .chunks(size)
+                .map(|chunk| Group::new(chunk.to_vec()))
+                .collect())
+        }
+        Grouping::Windows(size) => Ok(parse_rucksacks_with_scheme(input, scheme)?
+            .windows(size.get())
+            .map(|window| Group::new(window.to_vec()))
+            .collect()),
+        Grouping::KeyColumn => {
+            let mut groups: Vec<Group> = Vec::new();
+            let mut indexes: HashMap<String, usize> = HashMap::new();
+            for (key, rucksack) in parse_keyed_rucksacks_with_scheme(input, scheme)? {
+                let index = *indexes.entry(key.clone()).or_insert_with(|| {
+                    groups.push(Group {
+                        key: Some(key),
+                        rucksacks: Vec::new(),
+                    });
+                    groups.len() - 1
+                });
+                groups[index].rucksacks.push(rucksack);
+            }
+            Ok(groups)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroUsize;
+
+    use crate::{
+        parser::{
+            parse_groups, parse_groups_with_grouping, parse_groups_with_scheme,
+            parse_keyed_rucksacks, parse_rucksacks, parse_rucksacks_with_scheme, RucksackErrorKind,
+        },
+        Grouping, Item, PriorityTable,
+    };
+
+    fn size(size: usize) -> NonZeroUsize {
+        NonZeroUsize::new(size).unwrap()
+    }
+
+    #[test]
+    fn parse_valid_rucksacks() {
+        let rucksacks = parse_rucksacks("abcA\r\nzZ\nxyXY\r").unwrap();
+        let items: Vec<&str> = rucksacks.iter().map(|r| r.items.as_str()).collect();
+
+        assert_eq!(items, vec!["abcA", "zZ", "xyXY"]);
+        assert_eq!(parse_groups("ab\ncd\nef\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn odd_item_count() {
+        let error = parse_rucksacks("abcA\nabc\n").unwrap_err();
+
+        assert_eq!(error.kind, RucksackErrorKind::OddItemCount(3));
+        assert_eq!(error.location.line, 2);
+    }
+
+    #[test]
+    fn invalid_item() {
+        let error = parse_rucksacks("abcA\nab1d\n").unwrap_err();
+
+        assert_eq!(error.kind, RucksackErrorKind::InvalidItem('1'));
+        assert_eq!((error.location.line, error.location.column), (2, 3));
+        assert_eq!(error.location.snippet, "ab1d");
+    }
+
+    #[test]
+    fn empty_line() {
+        let error = parse_rucksacks("abcA\n\nabcd\n").unwrap_err();
+
+        assert_eq!(error.kind, RucksackErrorKind::EmptyLine);
+        assert_eq!(error.location.line, 2);
+
+        let rucksacks = parse_rucksacks("abcA\nabcd\n\r\n\n").unwrap();
+        assert_eq!(rucksacks.len(), 2);
+        assert_eq!(parse_keyed_rucksacks("north ab\n\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn stray_carriage_return() {
+        let error = parse_rucksacks("abcA\r\nab\rcd\r\n").unwrap_err();
+
+        assert_eq!(error.kind, RucksackErrorKind::InvalidItem('\r'));
+        assert_eq!((error.location.line, error.location.column), (2, 3));
+    }
+
+    #[test]
+    fn partial_group() {
+        let error = parse_groups("ab\ncd\nef\ngh\nij\n").unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            RucksackErrorKind::PartialGroup {
+                size: 3,
+                rucksacks: 2
+            }
+        );
+        assert_eq!(
+            (error.location.line, error.location.snippet.as_str()),
+            (4, "gh")
+        );
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        let input = "abcD\nDcEa\ncaXX\n";
+
+        let chunks = parse_groups_with_grouping(input, Grouping::Chunks(size(1))).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[2].common_item().items(),
+            vec![Item('a'), Item('c'), Item('X')]
+        );
+        let error = parse_groups_with_grouping(input, Grouping::Chunks(size(2))).unwrap_err();
+        assert_eq!(error.location.line, 3);
+
+        let windows = parse_groups_with_grouping(input, Grouping::Windows(size(2))).unwrap();
+        let common: Vec<Vec<Item>> = windows
+            .iter()
+            .map(|group| group.common_item().items())
+            .collect();
+        assert_eq!(
+            common,
+            vec![
+                vec![Item('a'), Item('c'), Item('D')],
+                vec![Item('a'), Item('c')]
+            ]
+        );
+        assert!(
+            parse_groups_with_grouping(input, Grouping::Windows(size(4)))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn group_by_key_column() {
+        let input = "north abcD\nsouth zzzz\nnorth\tDcEa\nsouth zZ\n";
+
+        let groups = parse_groups_with_grouping(input, Grouping::KeyColumn).unwrap();
+        let keys: Vec<&str> = groups
+            .iter()
+            .map(|group| group.key.as_deref().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["north", "south"]);
+        assert_eq!(
+            groups[0].common_item().items(),
+            vec![Item('a'), Item('c'), Item('D')]
+        );
+        assert_eq!(groups[1].common_item().items(), vec![Item('z')]);
+
+        let error = parse_keyed_rucksacks("north ab\n abcd\n").unwrap_err();
+        assert_eq!(error.kind, RucksackErrorKind::MissingKey);
+        assert_eq!(error.location.line, 2);
+        let error = parse_keyed_rucksacks("north ab\nsouth\n").unwrap_err();
+        assert_eq!(error.kind, RucksackErrorKind::EmptyLine);
+    }
+
+    #[test]
+    fn parse_with_priority_scheme() {
+        let scheme: PriorityTable = "a-z=1\nα-ω=27\n0-9=100".parse().unwrap();
+
+        let rucksacks = parse_rucksacks_with_scheme("aβ7βα7\nzz\n", &scheme).unwrap();
+        assert_eq!(rucksacks[0].left_compartment(), "aβ7");
+        assert_eq!(
+            rucksacks[0].duplicate_item().items(),
+            vec![Item('7'), Item('β')]
+        );
+
+        let error = parse_rucksacks_with_scheme("ab\naB\n", &scheme).unwrap_err();
+        assert_eq!(error.kind, RucksackErrorKind::InvalidItem('B'));
+        assert_eq!(error.location.line, 2);
+        let error = parse_rucksacks_with_scheme("aβα\n", &scheme).unwrap_err();
+        assert_eq!(error.kind, RucksackErrorKind::OddItemCount(3));
+
+        let groups =
+            parse_groups_with_scheme("ωa\nbω\n", Grouping::Chunks(size(2)), &scheme).unwrap();
+        assert_eq!(groups[0].common_item().items(), vec![Item('ω')]);
+    }
+}
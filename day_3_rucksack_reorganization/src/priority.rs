@@ -0,0 +1,223 @@
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use crate::Priority;
+
+/// The priority of each type of item.
+pub trait PriorityScheme {
+    /// Returns the priority of the item `c`, or `None` if `c` isn't an item in this scheme.
+    fn priority(&self, c: char) -> Option<Priority>;
+}
+
+/// The puzzle's priorities: lowercase items `a` through `z` have priorities 1 through 26, and uppercase items `A`
+/// through `Z` have priorities 27 through 52.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AocPriorities;
+
+/// Priorities listed in a table, such as one loaded from a file (see [`PriorityTable::from_str`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriorityTable(HashMap<char, Priority>);
+
+/// Error produced when constructing an [`Item`](crate::Item) from a char that isn't an item in the scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownItem(pub char);
+
+/// Error produced when parsing a [`PriorityTable`] fails.
+///
+/// Lines are numbered from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PriorityTableError {
+    /// The entry isn't of the form `ITEMS=PRIORITY`.
+    Malformed { line: usize, entry: String },
+    /// The items are neither a single char nor a range of chars like `a-z`.
+    InvalidItems { line: usize, items: String },
+    /// The priority isn't a number, or is too large for the items of a range.
+    InvalidPriority { line: usize, priority: String },
+    /// The item was already given a priority by an earlier entry.
+    Duplicate { line: usize, item: char },
+}
+
+impl PriorityScheme for AocPriorities {
+    fn priority(&self, c: char) -> Option<Priority> {
+        match c {
+            'a'..='z' => Some(c as Priority - 'a' as Priority + 1),
+            'A'..='Z' => Some(c as Priority - 'A' as Priority + 27),
+            _ => None,
+        }
+    }
+}
+
+impl PriorityScheme for PriorityTable {
+    fn priority(&self, c: char) -> Option<Priority> {
+        self.0.get(&c).copied()
+    }
+}
+
+impl PriorityTable {
+    /// Returns the number of items in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no items in the table.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for UnknownItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not an item in the priority scheme", self.0)
+    }
+}
+
+impl Error for UnknownItem {}
+
+impl Display for PriorityTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriorityTableError::Malformed { line, entry } => write!(
+                f,
+                "line {line}: expected an entry like `a-z=1` but found `{entry}`"
+            ),
+            PriorityTableError::InvalidItems { line, items } => write!(
+                f,
+                "line {line}: expected an item or a range of items like `a-z` but found `{items}`"
+            ),
+            PriorityTableError::InvalidPriority { line, priority } => {
+                write!(f, "line {line}: invalid priority `{priority}`")
+            }
+            PriorityTableError::Duplicate { line, item } => {
+                write!(f, "line {line}: item {item:?} already has a priority")
+            }
+        }
+    }
+}
+
+impl Error for PriorityTableError {}
+
+impl FromStr for PriorityTable {
+    type Err = PriorityTableError;
+
+    /// Parses a table with an `ITEMS=PRIORITY` entry on each line, where `ITEMS` is a single char, or a range of
+    /// chars like `a-z` that are given consecutive priorities starting at `PRIORITY`.
+    ///
+    /// Blank lines and anything after a `#` are ignored. For example, the table
+    ///
+    /// ```text
+    /// # Case-insensitive, then Greek letters, then a heavily weighted item
+    /// a-z=1
+    /// A-Z=1
+    /// α-ω=27
+    /// 7=1000
+    /// ```
+    ///
+    /// Items can't be whitespace, `#` or `=`, and can't be given more than one priority.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = HashMap::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let Some((items, priority)) = entry.split_once('=') else {
+                return Err(PriorityTableError::Malformed {
+                    line: line_number,
+                    entry: entry.to_string(),
+                });
+            };
+            let (items, priority) = (items.trim(), priority.trim());
+
+            let invalid_items = || PriorityTableError::InvalidItems {
+                line: line_number,
+                items: items.to_string(),
+            };
+            let (first, last) = match items.chars().collect::<Vec<char>>()[..] {
+                [item] => (item, item),
+                [first, '-', last] if first <= last => (first, last),
+                _ => return Err(invalid_items()),
+            };
+            if (first..=last).any(|c| c.is_whitespace() || c == '#' || c == '=') {
+                return Err(invalid_items());
+            }
+
+            let invalid_priority = || PriorityTableError::InvalidPriority {
+                line: line_number,
+                priority: priority.to_string(),
+            };
+            let first_priority: Priority = priority.parse().map_err(|_| invalid_priority())?;
+            for (offset, item) in (first..=last).enumerate() {
+                let priority = Priority::try_from(offset)
+                    .ok()
+                    .and_then(|offset| first_priority.checked_add(offset))
+                    .ok_or_else(invalid_priority)?;
+                if table.insert(item, priority).is_some() {
+                    return Err(PriorityTableError::Duplicate {
+                        line: line_number,
+                        item,
+                    });
+                }
+            }
+        }
+
+        Ok(PriorityTable(table))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::priority::{AocPriorities, PriorityScheme, PriorityTable, PriorityTableError};
+
+    #[test]
+    fn aoc_priorities() {
+        assert_eq!(AocPriorities.priority('a'), Some(1));
+        assert_eq!(AocPriorities.priority('Z'), Some(52));
+        assert_eq!(AocPriorities.priority('1'), None);
+        assert_eq!(AocPriorities.priority('é'), None);
+    }
+
+    #[test]
+    fn parse_priority_table() {
+        let table: PriorityTable =
+            "# Case-insensitive\na-z=1\nA-Z=1 # same as lowercase\n\nα-ω=100\n7=1000\n"
+                .parse()
+                .unwrap();
+
+        assert_eq!(table.priority('a'), Some(1));
+        assert_eq!(table.priority('B'), Some(2));
+        assert_eq!(table.priority('β'), Some(101));
+        assert_eq!(table.priority('7'), Some(1000));
+        assert_eq!(table.priority('8'), None);
+        assert_eq!(table.len(), 52 + 25 + 1);
+    }
+
+    #[test]
+    fn invalid_priority_tables() {
+        assert_eq!(
+            "a=1\nb".parse::<PriorityTable>(),
+            Err(PriorityTableError::Malformed {
+                line: 2,
+                entry: "b".to_string()
+            })
+        );
+        assert_eq!(
+            "z-a=1".parse::<PriorityTable>(),
+            Err(PriorityTableError::InvalidItems {
+                line: 1,
+                items: "z-a".to_string()
+            })
+        );
+        assert_eq!(
+            "a=one".parse::<PriorityTable>(),
+            Err(PriorityTableError::InvalidPriority {
+                line: 1,
+                priority: "one".to_string()
+            })
+        );
+        assert_eq!(
+            "a-z=1\nq=5".parse::<PriorityTable>(),
+            Err(PriorityTableError::Duplicate { line: 2, item: 'q' })
+        );
+    }
+}
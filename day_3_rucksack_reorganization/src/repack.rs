@@ -0,0 +1,219 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::Rucksack;
+
+/// Swap of the item at position `left` (from 0) in a rucksack's left compartment with the item at position `right`
+/// in its right compartment.
+///
+/// Compartments always hold half of the rucksack's items, so items are moved between them in swaps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Swap {
+    pub left: usize,
+    pub right: usize,
+}
+
+/// Swaps that leave no type of item in both compartments of a rucksack, and the rucksack they result in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepackPlan {
+    pub swaps: Vec<Swap>,
+    pub repacked: Rucksack,
+}
+
+impl RepackPlan {
+    /// Returns the number of items moved between compartments (two for each swap).
+    pub fn moves(&self) -> usize {
+        self.swaps.len() * 2
+    }
+}
+
+/// Returns the plan moving the fewest items between the compartments of the `rucksack` so that no type of item is in
+/// both of them, or `None` if that's impossible (e.g. `aaab`, where the `a`s can't fit in either compartment).
+///
+/// Every type of item has to end up entirely in one compartment, and the left compartment has to end up with half
+/// of the items. Choosing which types go left is a knapsack problem over the number of items in the left
+/// compartment, so this takes O(types × items) time.
+pub fn plan_repack(rucksack: &Rucksack) -> Option<RepackPlan> {
+    let left: Vec<char> = rucksack.left_compartment().chars().collect();
+    let right: Vec<char> = rucksack.right_compartment().chars().collect();
+
+    // Number of items of each type in the left and right compartments.
+    let mut counts: BTreeMap<char, (usize, usize)> = BTreeMap::new();
+    for &c in &left {
+        counts.entry(c).or_default().0 += 1;
+    }
+    for &c in &right {
+        counts.entry(c).or_default().1 += 1;
+    }
+    let counts: Vec<(char, (usize, usize))> = counts.into_iter().collect();
+
+    // fewest[size] is the fewest items moved to place the types considered so far, with `size` items of them in the
+    // left compartment. keep_left[type][size] records whether that type went left in the best placement.
+    let capacity = left.len();
+    let mut fewest: Vec<Option<usize>> = vec![None; capacity + 1];
+    fewest[0] = Some(0);
+    let mut keep_left: Vec<Vec<bool>> = Vec::with_capacity(counts.len());
+    for &(_, (in_left, in_right)) in &counts {
+        let mut next: Vec<Option<usize>> = vec![None; capacity + 1];
+        let mut went_left = vec![false; capacity + 1];
+        for (size, moves) in fewest.iter().enumerate() {
+            let Some(moves) = moves else {
+                continue;
+            };
+            // Place every item of the type right, moving the ones that are left.
+            if next[size].is_none_or(|best| moves + in_left < best) {
+                next[size] = Some(moves + in_left);
+                went_left[size] = false;
+            }
+            // Place every item of the type left, moving the ones that are right.
+            let left_size = size + in_left + in_right;
+            if left_size <= capacity && next[left_size].is_none_or(|best| moves + in_right < best) {
+                next[left_size] = Some(moves + in_right);
+                went_left[left_size] = true;
+            }
+        }
+        fewest = next;
+        keep_left.push(went_left);
+    }
+    fewest[capacity]?;
+
+    // Walk back through the placements to find which types go left.
+    let mut goes_left: Vec<char> = Vec::new();
+    let mut size = capacity;
+    for (&(c, (in_left, in_right)), went_left) in counts.iter().zip(&keep_left).rev() {
+        if went_left[size] {
+            goes_left.push(c);
+            size -= in_left + in_right;
+        }
+    }
+
+    // Pair each item leaving the left compartment with one leaving the right compartment; there are as many of
+    // each since the left compartment keeps its size.
+    let leaving_left = (0..left.len()).filter(|&position| !goes_left.contains(&left[position]));
+    let leaving_right = (0..right.len()).filter(|&position| goes_left.contains(&right[position]));
+    let swaps: Vec<Swap> = leaving_left
+        .zip(leaving_right)
+        .map(|(left, right)| Swap { left, right })
+        .collect();
+
+    let mut repacked = rucksack.clone();
+    for swap in &swaps {
+        repacked.swap_items(swap.left, swap.right);
+    }
+    Some(RepackPlan { swaps, repacked })
+}
+
+/// Returns the repack plan of each of the `rucksacks` (see [`plan_repack`]).
+pub fn plan_repacks(rucksacks: &[Rucksack]) -> Vec<Option<RepackPlan>> {
+    rucksacks.iter().map(plan_repack).collect()
+}
+
+impl Display for RepackPlan {
+    /// Lists the swaps, and the compartments they result in.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} moves:", self.moves())?;
+        let left: Vec<char> = self.repacked.left_compartment().chars().collect();
+        let right: Vec<char> = self.repacked.right_compartment().chars().collect();
+        for swap in &self.swaps {
+            // The items have been swapped in the repacked rucksack, so each is now in the other compartment.
+            writeln!(
+                f,
+                "  swap left {} ({}) with right {} ({})",
+                swap.left, right[swap.right], swap.right, left[swap.left]
+            )?;
+        }
+        writeln!(
+            f,
+            "  result: {} | {}",
+            self.repacked.left_compartment(),
+            self.repacked.right_compartment()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        repack::{plan_repack, Swap},
+        Rucksack,
+    };
+
+    fn rucksack(items: &str) -> Rucksack {
+        Rucksack {
+            items: items.to_string(),
+        }
+    }
+
+    #[test]
+    fn repack_rucksacks() {
+        let plan = plan_repack(&rucksack("abcd")).unwrap();
+        assert!(plan.swaps.is_empty());
+        assert_eq!(plan.repacked, rucksack("abcd"));
+
+        let plan = plan_repack(&rucksack("abcb")).unwrap();
+        assert_eq!(plan.moves(), 2);
+        assert_eq!(plan.swaps, vec![Swap { left: 1, right: 0 }]);
+        assert_eq!(plan.repacked, rucksack("acbb"));
+        assert!(plan.repacked.duplicate_items().is_empty());
+        assert_eq!(
+            plan.to_string(),
+            "2 moves:\n  swap left 1 (b) with right 0 (c)\n  result: ac | bb\n"
+        );
+
+        assert_eq!(plan_repack(&rucksack("aaab")), None);
+    }
+
+    #[test]
+    fn repack_moves_fewest_items() {
+        // Swapping one `x` with another item is enough.
+        let plan = plan_repack(&rucksack("abcxxdef")).unwrap();
+        assert_eq!(plan.moves(), 2);
+        assert!(plan.repacked.duplicate_items().is_empty());
+
+        // Brute force: the fewest moves over every choice of item types for the left compartment.
+        for items in [
+            "aabbaabb",
+            "abcabcabca",
+            "aabcbcdd",
+            "aaaabbbcdeeffg",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+        ] {
+            let rucksack = rucksack(items);
+            let plan = plan_repack(&rucksack);
+            assert_eq!(
+                plan.as_ref().map(|plan| plan.moves()),
+                brute_force_moves(&rucksack),
+                "repacking {items}"
+            );
+            if let Some(plan) = plan {
+                assert!(plan.repacked.duplicate_items().is_empty());
+                let sorted = |rucksack: &Rucksack| {
+                    let mut items: Vec<char> = rucksack.items.chars().collect();
+                    items.sort_unstable();
+                    items
+                };
+                assert_eq!(sorted(&plan.repacked), sorted(&rucksack));
+            }
+        }
+    }
+
+    /// Returns the fewest items moved to leave no type of item in both compartments, trying every subset of item
+    /// types in the left compartment.
+    fn brute_force_moves(rucksack: &Rucksack) -> Option<usize> {
+        let left: Vec<char> = rucksack.left_compartment().chars().collect();
+        let right: Vec<char> = rucksack.right_compartment().chars().collect();
+        let mut types: Vec<char> = left.iter().chain(&right).copied().collect();
+        types.sort_unstable();
+        types.dedup();
+
+        (0..1_usize << types.len())
+            .filter_map(|subset| {
+                let goes_left = |c: &char| subset & (1 << types.binary_search(c).unwrap()) != 0;
+                let left_size = left.iter().chain(&right).filter(|c| goes_left(c)).count();
+                (left_size == left.len()).then(|| {
+                    left.iter().filter(|c| !goes_left(c)).count()
+                        + right.iter().filter(|c| goes_left(c)).count()
+                })
+            })
+            .min()
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use crate::{Item, ItemSet};
+
+/// An item shared by several lists of items, and where it was found in each of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedItem {
+    pub item: Item,
+    /// Positions (from 0) of the item in each of the lists, in the order the lists were given.
+    pub positions: Vec<Vec<usize>>,
+}
+
+/// The items shared by several lists of items, such as a rucksack's compartments or a group's rucksacks.
+///
+/// Each rucksack should have exactly one duplicate item and each group exactly one common item, so the result
+/// distinguishes that case from finding none or several.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Shared {
+    None,
+    One(SharedItem),
+    /// Several shared items, in order of increasing priority.
+    Multiple(Vec<SharedItem>),
+}
+
+impl Shared {
+    /// Construct the `Shared` result for the `items` shared by every one of the `lists` of items.
+    pub(crate) fn new(items: ItemSet, lists: &[&str]) -> Shared {
+        let mut shared: Vec<SharedItem> = items
+            .iter()
+            .map(|item| SharedItem {
+                item,
+                positions: lists
+                    .iter()
+                    .map(|list| {
+                        list.chars()
+                            .enumerate()
+                            .filter(|&(_, c)| c == item.0)
+                            .map(|(position, _)| position)
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect();
+        match shared.len() {
+            0 => Shared::None,
+            1 => Shared::One(shared.remove(0)),
+            _ => Shared::Multiple(shared),
+        }
+    }
+
+    /// Returns the shared item if there is exactly one.
+    pub fn item(&self) -> Option<Item> {
+        match self {
+            Shared::One(shared) => Some(shared.item),
+            _ => None,
+        }
+    }
+
+    /// Returns every shared item, in order of increasing priority.
+    pub fn items(&self) -> Vec<Item> {
+        match self {
+            Shared::None => Vec::new(),
+            Shared::One(shared) => vec![shared.item],
+            Shared::Multiple(shared) => shared.iter().map(|shared| shared.item).collect(),
+        }
+    }
+}
+
+impl Display for SharedItem {
+    /// Shows the item and its positions in each list, e.g. "p (at 4; at 11)".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let positions: Vec<String> = self
+            .positions
+            .iter()
+            .map(|positions| {
+                let positions: Vec<String> = positions.iter().map(usize::to_string).collect();
+                format!("at {}", positions.join(", "))
+            })
+            .collect();
+        write!(f, "{} ({})", self.item.0, positions.join("; "))
+    }
+}
+
+impl Display for Shared {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shared::None => write!(f, "no shared items"),
+            Shared::One(shared) => write!(f, "1 shared item: {shared}"),
+            Shared::Multiple(shared) => {
+                let shared: Vec<String> = shared.iter().map(SharedItem::to_string).collect();
+                write!(f, "{} shared items: {}", shared.len(), shared.join(", "))
+            }
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{Assignment, Interval, Pair, Section};
+
+/// How many elves are assigned to each section of the camp, from the first section anyone is assigned to the last.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// Runs of consecutive sections assigned to the same number of elves, in order of their sections. Runs that
+    /// nobody is assigned to are included, but only between the first and last assigned sections.
+    pub segments: Vec<(Interval, usize)>,
+}
+
+/// Returns every elf's assignment in `pairs`, so the elves of the pair at index `i` are elves `2 * i` and `2 * i + 1`.
+pub fn assignments(pairs: &[Pair]) -> Vec<Assignment> {
+    pairs.iter().flat_map(|pair| [pair.0, pair.1]).collect()
+}
+
+impl Coverage {
+    /// Construct the `Coverage` of the `assignments` by sweeping across the sections where assignments start and
+    /// end, which takes O(n log n) time for n assignments.
+    pub fn new(assignments: &[Assignment]) -> Coverage {
+        // Number of elves assigned from each section where that number changes. Sections are widened so the
+        // section after `Section::MAX` can be represented.
+        let mut changes: BTreeMap<u64, i64> = BTreeMap::new();
+        for assignment in assignments {
+            *changes.entry(u64::from(assignment.start())).or_default() += 1;
+            *changes.entry(u64::from(assignment.end()) + 1).or_default() -= 1;
+        }
+
+        let mut segments: Vec<(Interval, usize)> = Vec::new();
+        let mut elves: i64 = 0;
+        let mut changes = changes.into_iter().peekable();
+        while let Some((start, change)) = changes.next() {
+            elves += change;
+            let Some(&(next, _)) = changes.peek() else {
+                break;
+            };
+            // The last segment ends before `next`, which is at most one past `Section::MAX`.
+            let end = (next - 1) as Section;
+            let elves = elves as usize;
+            // Assignments can end right where others start without changing the number of elves.
+            match segments.last_mut() {
+                Some((interval, last_elves)) if *last_elves == elves => {
+                    *interval = Interval::new(interval.start(), end)
+                        .expect("Changes are in increasing order of section");
+                }
+                _ => segments.push((
+                    Interval::new(start as Section, end)
+                        .expect("Changes are in increasing order of section"),
+                    elves,
+                )),
+            }
+        }
+        Coverage { segments }
+    }
+
+    /// Returns the number of sections assigned to each number of elves, including sections between the first and
+    /// last assigned sections that nobody is assigned to.
+    pub fn histogram(&self) -> BTreeMap<usize, u64> {
+        let mut histogram = BTreeMap::new();
+        for (interval, elves) in &self.segments {
+            *histogram.entry(*elves).or_default() += interval.len();
+        }
+        histogram
+    }
+
+    /// Returns the number of sections assigned to exactly `elves` elves.
+    pub fn sections_covered_by(&self, elves: usize) -> u64 {
+        self.histogram().get(&elves).copied().unwrap_or_default()
+    }
+
+    /// Returns the runs of sections between the first and last assigned sections that nobody is assigned to.
+    pub fn uncovered(&self) -> Vec<Interval> {
+        self.segments
+            .iter()
+            .filter(|(_, elves)| *elves == 0)
+            .map(|(interval, _)| *interval)
+            .collect()
+    }
+
+    /// Returns the runs of sections that at least one elf is assigned to, in order of their sections.
+    pub fn covered(&self) -> Vec<Interval> {
+        let mut covered: Vec<Interval> = Vec::new();
+        for (interval, _) in self.segments.iter().filter(|(_, elves)| *elves > 0) {
+            match covered.last_mut().and_then(|last| last.union(interval)) {
+                Some(union) => *covered.last_mut().expect("There is a last run") = union,
+                None => covered.push(*interval),
+            }
+        }
+        covered
+    }
+
+    /// Returns the total redundancy: the number of assignments of each section beyond the first elf assigned to it,
+    /// summed over every section.
+    pub fn redundancy(&self) -> u64 {
+        self.segments
+            .iter()
+            .map(|(interval, elves)| interval.len() * elves.saturating_sub(1) as u64)
+            .sum()
+    }
+
+    /// Returns the most elves assigned to the same section, and the runs of sections with that many elves.
+    ///
+    /// Returns `None` if there are no assignments.
+    pub fn max_overlap(&self) -> Option<(usize, Vec<Interval>)> {
+        let max = self.segments.iter().map(|(_, elves)| *elves).max()?;
+        let intervals = self
+            .segments
+            .iter()
+            .filter(|(_, elves)| *elves == max)
+            .map(|(interval, _)| *interval)
+            .collect();
+        Some((max, intervals))
+    }
+
+    /// Returns the elves (indexes into the `assignments` this coverage was constructed from) whose every section
+    /// is also assigned to another elf, so each of them could be removed on its own without leaving a section
+    /// uncovered.
+    ///
+    /// Removing several of them at once may leave sections uncovered; see [`minimum_cover`] for elves that can all
+    /// be removed together.
+    pub fn redundant_elves(&self, assignments: &[Assignment]) -> Vec<usize> {
+        let elves: Vec<usize> = self.segments.iter().map(|(_, elves)| *elves).collect();
+        let fewest = SparseTable::new(&elves);
+        assignments
+            .iter()
+            .enumerate()
+            .filter(|(_, assignment)| {
+                let first = self.segment_index(assignment.start());
+                let last = self.segment_index(assignment.end());
+                fewest.min(first, last) >= 2
+            })
+            .map(|(elf, _)| elf)
+            .collect()
+    }
+
+    /// Returns the index of the segment with the `section`.
+    fn segment_index(&self, section: Section) -> usize {
+        self.segments
+            .partition_point(|(interval, _)| interval.end() < section)
+    }
+}
+
+/// Returns the fewest elves (indexes into `assignments`) that are together assigned to every section any elf is
+/// assigned to, so every other elf could be removed without leaving a section uncovered.
+///
+/// This greedily picks, from the first uncovered section, the elf assigned furthest past it, which takes
+/// O(n log n) time for n assignments.
+pub fn minimum_cover(assignments: &[Assignment]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..assignments.len()).collect();
+    order.sort_by_key(|&elf| assignments[elf].start());
+
+    let mut cover = Vec::new();
+    // The next section to cover, widened so the section after `Section::MAX` can be represented.
+    let mut next: u64 = 0;
+    let mut i = 0;
+    while i < order.len() {
+        // Skip ahead to the next assigned section if there's a gap nobody is assigned to.
+        next = next.max(u64::from(assignments[order[i]].start()));
+        let mut best: Option<usize> = None;
+        while i < order.len() && u64::from(assignments[order[i]].start()) <= next {
+            let elf = order[i];
+            if best.is_none_or(|best| assignments[elf].end() > assignments[best].end()) {
+                best = Some(elf);
+            }
+            i += 1;
+        }
+        let best = best.expect("An assignment starts at or before the next section");
+        if u64::from(assignments[best].end()) >= next {
+            cover.push(best);
+            next = u64::from(assignments[best].end()) + 1;
+        }
+    }
+    cover.sort_unstable();
+    cover
+}
+
+/// Table answering minimum queries over ranges of a list in O(1) time, after O(n log n) time to build it.
+struct SparseTable {
+    /// `levels[k][i]` is the minimum of the `2^k` values starting at index `i`.
+    levels: Vec<Vec<usize>>,
+}
+
+impl SparseTable {
+    fn new(values: &[usize]) -> SparseTable {
+        let mut levels = vec![values.to_vec()];
+        let mut width = 1;
+        while width * 2 <= values.len() {
+            let previous = levels.last().expect("There is a first level");
+            let level = (0..=values.len() - width * 2)
+                .map(|i| previous[i].min(previous[i + width]))
+                .collect();
+            levels.push(level);
+            width *= 2;
+        }
+        SparseTable { levels }
+    }
+
+    /// Returns the minimum of the values from index `first` to `last`, inclusive.
+    fn min(&self, first: usize, last: usize) -> usize {
+        let level = (last - first + 1).ilog2() as usize;
+        self.levels[level][first].min(self.levels[level][last + 1 - (1 << level)])
+    }
+}
+
+impl Display for Coverage {
+    /// Summarizes the number of sections assigned to each number of elves, the gaps, and the busiest sections.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:>5} | {:>8}", "Elves", "Sections")?;
+        for (elves, sections) in self.histogram() {
+            writeln!(f, "{elves:>5} | {sections:>8}")?;
+        }
+
+        let uncovered: Vec<String> = self.uncovered().iter().map(Interval::to_string).collect();
+        if uncovered.is_empty() {
+            writeln!(f, "Every section between the first and last is covered.")?;
+        } else {
+            writeln!(f, "Sections nobody covers: {}", uncovered.join(", "))?;
+        }
+
+        if let Some((max, intervals)) = self.max_overlap() {
+            let intervals: Vec<String> = intervals.iter().map(Interval::to_string).collect();
+            writeln!(
+                f,
+                "At most {max} elves cover the same section, in sections {}",
+                intervals.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        coverage::{assignments, minimum_cover, Coverage},
+        parse_pairs, Assignment, Interval, Section,
+    };
+
+    fn interval(start: Section, end: Section) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    /// Returns a pseudorandom list of assignments within sections 1 to 40.
+    fn random_assignments(seed: u64, count: usize) -> Vec<Assignment> {
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % bound) as Section
+        };
+        (0..count)
+            .map(|_| {
+                let start = next(40) + 1;
+                let end = start + next(8);
+                interval(start, end)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example_coverage() {
+        let pairs = parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").unwrap();
+        let assignments = assignments(&pairs);
+
+        let coverage = Coverage::new(&assignments);
+
+        assert_eq!(coverage.segments.first(), Some(&(interval(2, 2), 4)));
+        assert_eq!(coverage.sections_covered_by(1), 1);
+        assert!(coverage.uncovered().is_empty());
+        assert_eq!(coverage.max_overlap(), Some((8, vec![interval(6, 6)])));
+        // Only the 5-7 and 7-9 assignments cover section 9.
+        let redundant = coverage.redundant_elves(&assignments);
+        assert!(!redundant.contains(&5));
+        assert_eq!(redundant.len(), assignments.len() - 1);
+        let cover = minimum_cover(&assignments);
+        assert_eq!(cover.len(), 2);
+        assert_eq!(Coverage::new(&[]).max_overlap(), None);
+    }
+
+    #[test]
+    fn gaps_and_extremes() {
+        let assignments = [interval(1, 3), interval(7, 8), interval(2, 3)];
+        let coverage = Coverage::new(&assignments);
+
+        assert_eq!(coverage.uncovered(), vec![interval(4, 6)]);
+        assert_eq!(coverage.covered(), vec![interval(1, 3), interval(7, 8)]);
+        assert_eq!(coverage.redundancy(), 2);
+        assert_eq!(coverage.redundant_elves(&assignments), vec![2]);
+        assert_eq!(minimum_cover(&assignments), vec![0, 1]);
+
+        let assignments = [
+            interval(0, Section::MAX),
+            interval(Section::MAX, Section::MAX),
+        ];
+        let coverage = Coverage::new(&assignments);
+        assert_eq!(coverage.sections_covered_by(1), u64::from(Section::MAX));
+        assert_eq!(coverage.sections_covered_by(2), 1);
+        assert_eq!(minimum_cover(&assignments), vec![0]);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..20 {
+            let assignments = random_assignments(seed, 12);
+            let coverage = Coverage::new(&assignments);
+
+            let count = |section: Section, skip: Option<usize>| {
+                assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|&(elf, assignment)| Some(elf) != skip && assignment.contains(section))
+                    .count()
+            };
+            let first = assignments.iter().map(Interval::start).min().unwrap();
+            let last = assignments.iter().map(Interval::end).max().unwrap();
+            let mut histogram = BTreeMap::new();
+            for section in first..=last {
+                *histogram.entry(count(section, None)).or_default() += 1;
+            }
+            assert_eq!(coverage.histogram(), histogram);
+
+            let redundant: Vec<usize> = (0..assignments.len())
+                .filter(|&elf| {
+                    let assignment = assignments[elf];
+                    (assignment.start()..=assignment.end())
+                        .all(|section| count(section, Some(elf)) > 0)
+                })
+                .collect();
+            assert_eq!(coverage.redundant_elves(&assignments), redundant);
+
+            // The cover keeps every section covered, and no single elf of it can be dropped.
+            let cover = minimum_cover(&assignments);
+            let kept: Vec<Assignment> = cover.iter().map(|&elf| assignments[elf]).collect();
+            assert_eq!(Coverage::new(&kept).uncovered(), coverage.uncovered());
+            assert_eq!(
+                Coverage::new(&kept)
+                    .segments
+                    .iter()
+                    .map(|(interval, _)| interval.len())
+                    .sum::<u64>(),
+                coverage
+                    .segments
+                    .iter()
+                    .map(|(interval, _)| interval.len())
+                    .sum::<u64>()
+            );
+            assert!(Coverage::new(&kept).redundant_elves(&kept).is_empty());
+            // Neighbouring segments always have different numbers of elves.
+            assert!(coverage
+                .segments
+                .windows(2)
+                .all(|pair| pair[0].1 != pair[1].1));
+        }
+    }
+}
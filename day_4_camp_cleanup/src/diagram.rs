@@ -0,0 +1,200 @@
+use std::fmt::Write;
+
+use crate::{Assignment, Interval, Pair, Section};
+
+/// ANSI escape code starting the highlight of sections assigned to both elves of a pair.
+const HIGHLIGHT: &str = "\x1b[1;33m";
+
+/// ANSI escape code ending a highlight.
+const RESET: &str = "\x1b[0m";
+
+/// Draws pairs of assignments like the puzzle does, with a row of sections for each elf:
+///
+/// ```text
+/// .234.....  2-4
+/// .....678.  6-8
+/// ```
+///
+/// Sections assigned to both elves are highlighted, with ANSI colors or a row of `^` markers below the pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diagram {
+    /// Most columns to draw sections in. When there are more sections than columns, each column stands for several
+    /// sections and is drawn as `#` if any of them are assigned.
+    pub width: usize,
+    /// Whether to highlight sections with ANSI colors.
+    pub color: bool,
+}
+
+/// Sections drawn in each column of a diagram.
+struct Scale {
+    first: u64,
+    last: u64,
+    per_column: u64,
+}
+
+impl Default for Diagram {
+    /// 80 columns, without colors.
+    fn default() -> Self {
+        Diagram {
+            width: 80,
+            color: false,
+        }
+    }
+}
+
+impl Scale {
+    /// Construct the `Scale` drawing every section of the `pairs` in at most `width` columns.
+    fn new(pairs: &[Pair], width: usize) -> Option<Scale> {
+        let assignments = pairs.iter().flat_map(|pair| [pair.0, pair.1]);
+        let first = u64::from(assignments.clone().map(|a| a.start()).min()?);
+        let last = u64::from(assignments.map(|a| a.end()).max()?);
+        let per_column = (last - first + 1).div_ceil(width as u64);
+        Some(Scale {
+            first,
+            last,
+            per_column,
+        })
+    }
+
+    /// Returns the sections drawn in each column.
+    fn columns(&self) -> impl Iterator<Item = Interval> + '_ {
+        (self.first..=self.last)
+            .step_by(self.per_column as usize)
+            .map(|start| {
+                let end = (start + self.per_column - 1).min(self.last);
+                Interval::new(start as Section, end as Section).expect("Columns are nonempty")
+            })
+    }
+}
+
+impl Diagram {
+    /// Returns the drawing of the `pairs`, each followed by how its assignments relate and whether they're
+    /// redundant.
+    ///
+    /// Every pair is drawn across the same sections, from the first section of any of them to the last.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width is 0.
+    pub fn draw(&self, pairs: &[Pair]) -> String {
+        assert!(self.width > 0, "The diagram must be at least 1 column wide");
+        let Some(scale) = Scale::new(pairs, self.width) else {
+            return String::new();
+        };
+
+        let mut drawing = String::new();
+        if scale.per_column == 1 {
+            writeln!(drawing, "Sections {}-{}:", scale.first, scale.last)
+        } else {
+            writeln!(
+                drawing,
+                "Sections {}-{}, {} per column:",
+                scale.first, scale.last, scale.per_column
+            )
+        }
+        .expect("Writing to a String can't fail");
+
+        for pair in pairs {
+            let shared = pair.0.intersection(&pair.1);
+            let redundancy = if pair.is_fully_redundant() {
+                "fully redundant"
+            } else if pair.is_partly_redundant() {
+                "partly redundant"
+            } else {
+                "not redundant"
+            };
+            let rows = [
+                (pair.0, String::new()),
+                (pair.1, format!("  {}, {redundancy}", pair.relation())),
+            ];
+            for (assignment, note) in rows {
+                let row = self.row(&scale, &assignment, shared.as_ref());
+                writeln!(drawing, "{row}  {assignment}{note}")
+                    .expect("Writing to a String can't fail");
+            }
+            if let (Some(shared), false) = (shared, self.color) {
+                let markers: String = scale
+                    .columns()
+                    .map(|column| if column.intersects(&shared) { '^' } else { ' ' })
+                    .collect();
+                writeln!(drawing, "{}", markers.trim_end())
+                    .expect("Writing to a String can't fail");
+            }
+            drawing.push('\n');
+        }
+        drawing
+    }
+
+    /// Returns the row of columns for the `assignment`, highlighting any `shared` with the other elf of its pair.
+    fn row(&self, scale: &Scale, assignment: &Assignment, shared: Option<&Interval>) -> String {
+        let mut row = String::new();
+        for column in scale.columns() {
+            let cell = if !column.intersects(assignment) {
+                '.'
+            } else if scale.per_column == 1 {
+                char::from_digit(column.start() % 10, 10).expect("Digits are below 10")
+            } else {
+                '#'
+            };
+            if self.color && shared.is_some_and(|shared| column.intersects(shared)) {
+                write!(row, "{HIGHLIGHT}{cell}{RESET}").expect("Writing to a String can't fail");
+            } else {
+                row.push(cell);
+            }
+        }
+        row
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{diagram::Diagram, parse_pairs};
+
+    #[test]
+    fn draw_example_pairs() {
+        let pairs = parse_pairs("2-4,6-8\n2-8,3-7\n5-7,7-9\n").unwrap();
+
+        assert_eq!(
+            Diagram::default().draw(&pairs),
+            "Sections 2-9:\n\
+             234.....  2-4\n\
+             ....678.  6-8  before, not redundant\n\
+             \n\
+             2345678.  2-8\n\
+             .34567..  3-7  contains, fully redundant\n\
+             \x20^^^^^\n\
+             \n\
+             ...567..  5-7\n\
+             .....789  7-9  overlaps, partly redundant\n\
+             \x20    ^\n\
+             \n"
+        );
+
+        let colored = Diagram {
+            width: 80,
+            color: true,
+        }
+        .draw(&pairs[2..]);
+        assert_eq!(colored.lines().nth(1), Some("56\x1b[1;33m7\x1b[0m..  5-7"));
+        assert!(!colored.contains('^'));
+        assert_eq!(Diagram::default().draw(&[]), "");
+    }
+
+    #[test]
+    fn compress_wide_pairs() {
+        let pairs = parse_pairs("1-50,40-100\n").unwrap();
+        let diagram = Diagram {
+            width: 10,
+            color: false,
+        };
+
+        assert_eq!(
+            diagram.draw(&pairs),
+            "Sections 1-100, 10 per column:\n\
+             #####.....  1-50\n\
+             ...#######  40-100  overlaps, partly redundant\n\
+             \x20  ^^\n\
+             \n"
+        );
+    }
+}
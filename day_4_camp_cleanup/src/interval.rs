@@ -0,0 +1,325 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::Section;
+
+/// Nonempty range of sections, including both its start and end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: Section,
+    end: Section,
+}
+
+/// How one interval is placed relative to another: one of Allen's 13 interval relations.
+///
+/// Sections are discrete, so an interval "meets" another when it ends in the section right before the other starts,
+/// and "overlaps" it when they share at least one section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// Ends at least one section before the other starts.
+    Before,
+    /// Ends in the section right before the other starts.
+    Meets,
+    /// Starts before the other and ends inside it.
+    Overlaps,
+    /// Starts with the other and ends before it.
+    Starts,
+    /// Starts after the other and ends before it.
+    During,
+    /// Starts after the other and ends with it.
+    Finishes,
+    /// Starts and ends with the other.
+    Equals,
+    /// Starts before the other and ends with it.
+    FinishedBy,
+    /// Starts before the other and ends after it.
+    Contains,
+    /// Starts with the other and ends after it.
+    StartedBy,
+    /// Starts inside the other and ends after it.
+    OverlappedBy,
+    /// Starts in the section right after the other ends.
+    MetBy,
+    /// Starts at least one section after the other ends.
+    After,
+}
+
+impl Interval {
+    /// Construct the `Interval` from `start` to `end`, or `None` if `start` is after `end`.
+    pub fn new(start: Section, end: Section) -> Option<Interval> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// Returns the first section of the interval.
+    pub fn start(&self) -> Section {
+        self.start
+    }
+
+    /// Returns the last section of the interval.
+    pub fn end(&self) -> Section {
+        self.end
+    }
+
+    /// Returns the number of sections in the interval.
+    pub fn len(&self) -> u64 {
+        u64::from(self.end - self.start) + 1
+    }
+
+    /// Returns false, since intervals have at least one section.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns true if the `section` is in the interval.
+    pub fn contains(&self, section: Section) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Returns true if every section of the `other` interval is in this interval.
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Returns true if the intervals share at least one section.
+    pub fn intersects(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Returns true if one interval ends in the section right before the other starts.
+    pub fn is_adjacent(&self, other: &Interval) -> bool {
+        self.end.checked_add(1) == Some(other.start) || other.end.checked_add(1) == Some(self.start)
+    }
+
+    /// Returns the sections in both intervals, or `None` if they don't share any.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Returns the sections in either interval, or `None` if there are sections between them that are in neither.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        (self.intersects(other) || self.is_adjacent(other)).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// Returns the sections in this interval but not the `other` interval: none, the part before or after the
+    /// `other` interval, or both parts if the `other` interval is inside this one.
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+        let before = other
+            .start
+            .checked_sub(1)
+            .and_then(|end| Interval::new(self.start, end));
+        let after = other
+            .end
+            .checked_add(1)
+            .and_then(|start| Interval::new(start, self.end));
+        before.into_iter().chain(after).collect()
+    }
+
+    /// Returns how this interval is placed relative to the `other` interval.
+    pub fn relation(&self, other: &Interval) -> Relation {
+        use std::cmp::Ordering::*;
+
+        if self.end < other.start {
+            return if self.is_adjacent(other) {
+                Relation::Meets
+            } else {
+                Relation::Before
+            };
+        }
+        if other.end < self.start {
+            return if self.is_adjacent(other) {
+                Relation::MetBy
+            } else {
+                Relation::After
+            };
+        }
+        match (self.start.cmp(&other.start), self.end.cmp(&other.end)) {
+            (Less, Less) => Relation::Overlaps,
+            (Less, Equal) => Relation::FinishedBy,
+            (Less, Greater) => Relation::Contains,
+            (Equal, Less) => Relation::Starts,
+            (Equal, Equal) => Relation::Equals,
+            (Equal, Greater) => Relation::StartedBy,
+            (Greater, Less) => Relation::During,
+            (Greater, Equal) => Relation::Finishes,
+            (Greater, Greater) => Relation::OverlappedBy,
+        }
+    }
+}
+
+impl Relation {
+    /// Every `Relation`, in order of where the first interval starts relative to the second.
+    pub const ALL: [Relation; 13] = [
+        Relation::Before,
+        Relation::Meets,
+        Relation::Overlaps,
+        Relation::FinishedBy,
+        Relation::Contains,
+        Relation::Starts,
+        Relation::Equals,
+        Relation::StartedBy,
+        Relation::During,
+        Relation::Finishes,
+        Relation::OverlappedBy,
+        Relation::MetBy,
+        Relation::After,
+    ];
+
+    /// Returns the relation of the second interval to the first, e.g. `After` for `Before`.
+    pub fn inverse(&self) -> Relation {
+        match self {
+            Relation::Before => Relation::After,
+            Relation::Meets => Relation::MetBy,
+            Relation::Overlaps => Relation::OverlappedBy,
+            Relation::Starts => Relation::StartedBy,
+            Relation::During => Relation::Contains,
+            Relation::Finishes => Relation::FinishedBy,
+            Relation::Equals => Relation::Equals,
+            Relation::FinishedBy => Relation::Finishes,
+            Relation::Contains => Relation::During,
+            Relation::StartedBy => Relation::Starts,
+            Relation::OverlappedBy => Relation::Overlaps,
+            Relation::MetBy => Relation::Meets,
+            Relation::After => Relation::Before,
+        }
+    }
+
+    /// Returns true if either interval contains the other.
+    pub fn is_containment(&self) -> bool {
+        matches!(
+            self,
+            Relation::Starts
+                | Relation::During
+                | Relation::Finishes
+                | Relation::Equals
+                | Relation::FinishedBy
+                | Relation::Contains
+                | Relation::StartedBy
+        )
+    }
+
+    /// Returns true if the intervals share at least one section.
+    pub fn is_intersecting(&self) -> bool {
+        !matches!(
+            self,
+            Relation::Before | Relation::Meets | Relation::MetBy | Relation::After
+        )
+    }
+}
+
+impl From<Interval> for RangeInclusive<Section> {
+    fn from(interval: Interval) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+impl TryFrom<RangeInclusive<Section>> for Interval {
+    type Error = RangeInclusive<Section>;
+
+    /// Converts the `range`, returning it as the error if it's empty.
+    fn try_from(range: RangeInclusive<Section>) -> Result<Self, Self::Error> {
+        Interval::new(*range.start(), *range.end()).ok_or(range)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Relation::Before => "before",
+            Relation::Meets => "meets",
+            Relation::Overlaps => "overlaps",
+            Relation::Starts => "starts",
+            Relation::During => "during",
+            Relation::Finishes => "finishes",
+            Relation::Equals => "equals",
+            Relation::FinishedBy => "finished by",
+            Relation::Contains => "contains",
+            Relation::StartedBy => "started by",
+            Relation::OverlappedBy => "overlapped by",
+            Relation::MetBy => "met by",
+            Relation::After => "after",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interval::{Interval, Relation};
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn interval_operations() {
+        let a = interval(2, 6);
+        let b = interval(5, 9);
+
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(a.len(), 5);
+        assert_eq!(interval(0, u32::MAX).len(), 1 << 32);
+        assert!(a.contains(6) && !a.contains(7));
+        assert!(a.contains_interval(&interval(3, 6)));
+        assert!(!a.contains_interval(&b));
+        assert_eq!(a.intersection(&b), Some(interval(5, 6)));
+        assert_eq!(a.intersection(&interval(7, 9)), None);
+        assert_eq!(a.union(&b), Some(interval(2, 9)));
+        assert_eq!(a.union(&interval(7, 9)), Some(interval(2, 9)));
+        assert_eq!(a.union(&interval(8, 9)), None);
+        assert!(a.is_adjacent(&interval(7, 7)) && interval(0, 1).is_adjacent(&a));
+        assert!(!a.is_adjacent(&b));
+        assert_eq!(a.difference(&b), vec![interval(2, 4)]);
+        assert_eq!(
+            a.difference(&interval(3, 4)),
+            vec![interval(2, 2), interval(5, 6)]
+        );
+        assert_eq!(a.difference(&interval(0, 9)), vec![]);
+        assert_eq!(a.difference(&interval(8, 9)), vec![a]);
+        assert_eq!(
+            interval(0, 3).difference(&interval(0, 1)),
+            vec![interval(2, 3)]
+        );
+        assert_eq!(a.to_string(), "2-6");
+    }
+
+    #[test]
+    fn allen_relations() {
+        let b = interval(4, 6);
+        let cases = [
+            (interval(1, 2), Relation::Before),
+            (interval(1, 3), Relation::Meets),
+            (interval(2, 5), Relation::Overlaps),
+            (interval(2, 6), Relation::FinishedBy),
+            (interval(2, 7), Relation::Contains),
+            (interval(4, 5), Relation::Starts),
+            (interval(4, 6), Relation::Equals),
+            (interval(4, 7), Relation::StartedBy),
+            (interval(5, 5), Relation::During),
+            (interval(5, 6), Relation::Finishes),
+            (interval(5, 7), Relation::OverlappedBy),
+            (interval(7, 8), Relation::MetBy),
+            (interval(8, 9), Relation::After),
+        ];
+
+        for (relation, (a, expected)) in Relation::ALL.iter().zip(cases) {
+            assert_eq!(*relation, expected);
+            assert_eq!(a.relation(&b), expected, "{a} {expected} {b}");
+            assert_eq!(b.relation(&a), expected.inverse());
+            assert_eq!(
+                expected.is_containment(),
+                a.contains_interval(&b) || b.contains_interval(&a)
+            );
+            assert_eq!(expected.is_intersecting(), a.intersects(&b));
+        }
+    }
+}
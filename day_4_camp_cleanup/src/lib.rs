@@ -0,0 +1,160 @@
+use std::{fmt::Display, fs};
+
+pub use coverage::{assignments, minimum_cover, Coverage};
+pub use diagram::Diagram;
+pub use interval::{Interval, Relation};
+pub use parser::{parse_pairs, parse_pairs_with_mode, PairError, PairErrorKind, RangeMode};
+pub use reassign::{reassign, Constraints, Reassignment, TooFewElves};
+
+mod coverage;
+mod diagram;
+mod interval;
+mod parser;
+mod reassign;
+
+pub static INPUT_FILE: &str = "inputs/day4.txt";
+
+/// A range of `Section` IDs that an Elf is assigned to.
+pub type Assignment = Interval;
+
+/// A `Pair` of two Elves's assignments.
+pub struct Pair(pub Assignment, pub Assignment);
+
+/// Section ID that an Elf is assigned to.
+pub type Section = u32;
+
+/// Number of pairs of assignments in each [`Relation`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelationReport {
+    /// Number of pairs in each relation, in the order of [`Relation::ALL`].
+    pub counts: [usize; 13],
+}
+
+impl Pair {
+    /// Returns how the first assignment is placed relative to the second.
+    pub fn relation(&self) -> Relation {
+        self.0.relation(&self.1)
+    }
+
+    /// Returns whether the pair's assignments are fully redundant.
+    ///
+    /// This returns true when one of the assignments's section IDs are all included in the other assignment.
+    pub fn is_fully_redundant(&self) -> bool {
+        self.relation().is_containment()
+    }
+
+    /// Returns whether the pair's assignments are partly redundant.
+    ///
+    /// This returns true when a section ID from one assignments is included in the other assignment.
+    pub fn is_partly_redundant(&self) -> bool {
+        self.relation().is_intersecting()
+    }
+}
+
+impl RelationReport {
+    /// Construct the `RelationReport` counting the relation of each of the `pairs`.
+    pub fn new(pairs: &[Pair]) -> RelationReport {
+        let mut report = RelationReport::default();
+        for pair in pairs {
+            let relation = pair.relation();
+            let index = Relation::ALL
+                .iter()
+                .position(|r| *r == relation)
+                .expect("Relation::ALL has every relation");
+            report.counts[index] += 1;
+        }
+        report
+    }
+
+    /// Returns the number of pairs in the `relation`.
+    pub fn count(&self, relation: Relation) -> usize {
+        Relation::ALL
+            .iter()
+            .zip(self.counts)
+            .find_map(|(r, count)| (*r == relation).then_some(count))
+            .unwrap_or_default()
+    }
+}
+
+impl Display for RelationReport {
+    /// Shows the number of pairs in each relation, and the share of all pairs that is.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total: usize = self.counts.iter().sum();
+        writeln!(f, "{:>13} | {:>5} | {:>6}", "Relation", "Pairs", "Share")?;
+        for (relation, count) in Relation::ALL.iter().zip(self.counts) {
+            let share = if total == 0 {
+                0.0
+            } else {
+                count as f64 / total as f64 * 100.0
+            };
+            writeln!(f, "{relation:>13} | {count:>5} | {share:>5.1}%")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of fully redunant pairs in `pairs`.
+pub fn fully_redundant_pairs(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| p.is_fully_redundant()).count()
+}
+
+/// Returns the number of partly redunant pairs in `pairs`.
+pub fn partly_redundant_pairs(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| p.is_partly_redundant()).count()
+}
+
+/// Returns the pairs parsed from `input`, panicking with the location of any parse error.
+fn pairs(input: &str) -> Vec<Pair> {
+    parse_pairs(input).unwrap_or_else(|e| panic!("Failed to parse input file\n{e}"))
+}
+
+/// Returns the number of fully redunant pairs found in the `input_file`.
+///
+/// This solves Day 4 Part 1.
+pub fn count_fully_redundant_pairs(input_file: &str) -> usize {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    fully_redundant_pairs(&pairs(&input))
+}
+
+/// Returns the number of partly redunant pairs found in the `input_file`.
+///
+/// This solves Day 4 Part 2.
+pub fn count_partly_redundant_pairs(input_file: &str) -> usize {
+    let input = fs::read_to_string(input_file).expect("Failed to read input file");
+    partly_redundant_pairs(&pairs(&input))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        count_fully_redundant_pairs, count_partly_redundant_pairs, parse_pairs, Relation,
+        RelationReport, INPUT_FILE,
+    };
+
+    #[test]
+    fn solve_day_4_part_1() {
+        assert_eq!(count_fully_redundant_pairs(INPUT_FILE), 602);
+    }
+
+    #[test]
+    fn solve_day_4_part_2() {
+        assert_eq!(count_partly_redundant_pairs(INPUT_FILE), 891);
+    }
+
+    #[test]
+    fn relation_report() {
+        let pairs = parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").unwrap();
+
+        let report = RelationReport::new(&pairs);
+
+        assert_eq!(report.count(Relation::Before), 1);
+        assert_eq!(report.count(Relation::Meets), 1);
+        assert_eq!(report.count(Relation::Overlaps), 2);
+        assert_eq!(report.count(Relation::Contains), 1);
+        assert_eq!(report.count(Relation::Finishes), 1);
+        assert_eq!(report.counts.iter().sum::<usize>(), pairs.len());
+        assert!(report
+            .to_string()
+            .contains("     overlaps |     2 |  33.3%\n"));
+    }
+}
@@ -1,93 +1,165 @@
-use std::{fs, ops::RangeInclusive};
+use std::{
+    env, error::Error, fs, num::NonZeroU64, ops::RangeInclusive, path::PathBuf, process::ExitCode,
+};
 
-use parser::parse_pairs;
+use clap::Parser;
 
-mod parser;
+use day_4_camp_cleanup::{
+    assignments, fully_redundant_pairs, minimum_cover, parse_pairs_with_mode,
+    partly_redundant_pairs, reassign, Constraints, Coverage, Diagram, RangeMode, RelationReport,
+    INPUT_FILE,
+};
 
-static INPUT_FILE: &str = "inputs/day4.txt";
+/// Find the redundant section assignments of the pairs of elves.
+#[derive(Parser)]
+struct Args {
+    /// Path to the puzzle input.
+    #[arg(default_value = INPUT_FILE)]
+    input: PathBuf,
 
-/// A range of `Section` IDs that an Elf is assigned to.
-type Assignment = RangeInclusive<Section>;
+    /// Print the number of pairs in each of Allen's interval relations.
+    #[arg(long)]
+    relations: bool,
 
-/// A `Pair` of two Elves's assignments.
-struct Pair(Assignment, Assignment);
+    /// Print how many elves are assigned to each section across the whole camp, and which elves could be removed.
+    #[arg(long)]
+    coverage: bool,
 
-/// Section ID that an Elf is assigned to.
-type Section = u32;
+    /// Print new assignments for the elves that cover the same sections with the least redundancy.
+    #[arg(long)]
+    reassign: bool,
 
-impl Pair {
-    /// Returns whether the pair's assignments are fully redundant.
-    ///
-    /// This returns true when one of the assignments's section IDs are all included in the other assignment.
-    fn is_fully_redundant(&self) -> bool {
-        // If either assignment range contains the start and end of the other assignment, the pair is redundant.
-        if self.0.contains(self.1.start()) && self.0.contains(self.1.end()) {
-            return true;
-        };
-        if self.1.contains(self.0.start()) && self.1.contains(self.0.end()) {
-            return true;
-        };
-        false
-    }
+    /// Most sections an elf can be reassigned.
+    #[arg(long, value_name = "SECTIONS", requires = "reassign")]
+    max_len: Option<NonZeroU64>,
 
-    /// Returns whether the pair's assignments are partly redundant.
-    ///
-    /// This returns true when a section ID from one assignments is included in the other assignment.
-    fn is_partly_redundant(&self) -> bool {
-        if self.0.contains(self.1.start()) || self.0.contains(self.1.end()) {
-            return true;
-        };
-        if self.1.contains(self.0.start()) || self.1.contains(self.0.end()) {
-            return true;
-        };
-        false
-    }
-}
+    /// Allow an elf to be reassigned several runs of sections, rather than a single run.
+    #[arg(long, requires = "reassign")]
+    split: bool,
 
-/// Returns the number of fully redunant pairs found in the `input_file`.
-///
-/// This solves Day 4 Part 1.
-fn count_fully_redundant_pairs(input_file: &str) -> usize {
-    let input = fs::read_to_string(input_file).expect("Failed to read input file");
-    parse_pairs(&input)
-        .iter()
-        .filter(|p| p.is_fully_redundant())
-        .count()
-}
+    /// Reassign every elf at least one section, even if they have to share it, rather than leaving some without any.
+    #[arg(long, requires = "reassign")]
+    assign_every_elf: bool,
+
+    /// Draw the pairs' assignments like the puzzle does, highlighting the sections both elves of a pair are assigned.
+    #[arg(long)]
+    draw: bool,
+
+    /// Draw only the pairs on these lines of the input, like `1-6`.
+    #[arg(long, value_name = "FIRST-LAST", requires = "draw", value_parser = line_range)]
+    pairs: Option<RangeInclusive<usize>>,
+
+    /// Highlight the drawing with ANSI colors.
+    #[arg(long, requires = "draw")]
+    color: bool,
 
-/// Returns the number of partly redunant pairs found in the `input_file`.
-///
-/// This solves Day 4 Part 2.
-fn count_partly_redundant_pairs(input_file: &str) -> usize {
-    let input = fs::read_to_string(input_file).expect("Failed to read input file");
-    parse_pairs(&input)
-        .iter()
-        .filter(|p| p.is_partly_redundant())
-        .count()
+    /// Terminal width to fit the drawing in; defaults to `$COLUMNS`, or 80.
+    #[arg(long, requires = "draw")]
+    width: Option<usize>,
+
+    /// Read assignments that start after they end, like `6-4`, as if their sections were swapped.
+    #[arg(long)]
+    lenient: bool,
 }
 
-fn main() {
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let input = fs::read_to_string(&args.input)?;
+    let mode = if args.lenient {
+        RangeMode::Lenient
+    } else {
+        RangeMode::Strict
+    };
+    let pairs = parse_pairs_with_mode(&input, mode)?;
+
     println!(
         "The assignment list has {} fully redundant pairs of assignments.",
-        count_fully_redundant_pairs(INPUT_FILE)
+        fully_redundant_pairs(&pairs)
     );
     println!(
         "The assignment list has {} partly redundant pairs of assignments.",
-        count_partly_redundant_pairs(INPUT_FILE)
+        partly_redundant_pairs(&pairs)
     );
+    if args.relations {
+        println!("\n{}", RelationReport::new(&pairs));
+    }
+    if args.coverage {
+        let assignments = assignments(&pairs);
+        let coverage = Coverage::new(&assignments);
+        println!("\n{coverage}");
+        println!(
+            "{} of {} elves could each be removed without leaving a section uncovered.",
+            coverage.redundant_elves(&assignments).len(),
+            assignments.len()
+        );
+        println!(
+            "{} elves are enough to cover every section that is covered.",
+            minimum_cover(&assignments).len()
+        );
+    }
+    if args.reassign {
+        let assignments = assignments(&pairs);
+        let constraints = Constraints {
+            max_len: args.max_len,
+            contiguous: !args.split,
+            assign_every_elf: args.assign_every_elf,
+        };
+        let reassignment = reassign(&assignments, &constraints)?;
+        println!(
+            "\nReassigning the elves cuts the redundancy from {} to {} sections:",
+            Coverage::new(&assignments).redundancy(),
+            reassignment.redundancy()
+        );
+        print!("{reassignment}");
+    }
+    if args.draw {
+        let selected = match args.pairs {
+            Some(lines) => pairs
+                .get(lines.start() - 1..*lines.end())
+                .ok_or_else(|| format!("The input only has {} pairs", pairs.len()))?,
+            None => &pairs[..],
+        };
+        let width = args
+            .width
+            .or_else(|| env::var("COLUMNS").ok()?.parse().ok())
+            .unwrap_or(80);
+        let diagram = Diagram {
+            // Leave room for the assignments and their relation after the sections.
+            width: width.saturating_sub(LABEL_WIDTH).max(10),
+            color: args.color,
+        };
+        print!("\n{}", diagram.draw(selected));
+    }
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use crate::{count_fully_redundant_pairs, count_partly_redundant_pairs, INPUT_FILE};
+/// Columns drawn after the sections of each pair, for its assignments and their relation.
+const LABEL_WIDTH: usize = 40;
 
-    #[test]
-    fn solve_day_4_part_1() {
-        assert_eq!(count_fully_redundant_pairs(INPUT_FILE), 602);
+/// Parses a range of input lines like `1-6`, numbered from 1.
+fn line_range(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let (first, last) = s
+        .split_once('-')
+        .ok_or_else(|| format!("expected a range of lines like `1-6` but found `{s}`"))?;
+    let first: usize = first
+        .parse()
+        .map_err(|e| format!("invalid line `{first}`: {e}"))?;
+    let last: usize = last
+        .parse()
+        .map_err(|e| format!("invalid line `{last}`: {e}"))?;
+    if first == 0 || first > last {
+        return Err(format!(
+            "expected lines numbered from 1, with the first no greater than the last, but found `{s}`"
+        ));
     }
+    Ok(first..=last)
+}
 
-    #[test]
-    fn solve_day_4_part_2() {
-        assert_eq!(count_partly_redundant_pairs(INPUT_FILE), 891);
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
     }
 }
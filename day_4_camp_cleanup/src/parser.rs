@@ -1,62 +1,302 @@
 #![allow(clippy::result_large_err)]
 
+use std::{error::Error, fmt::Display};
+
 use pear::{
-    combinators::*,
-    input::{Pear, Text},
+    error::Expected,
+    input::{Input as _, ParserInfo, Pear, Rewind, Show, Span, Text},
     macros::{parse_error, parser},
-    parse,
     parsers::*,
 };
 
+use parse_error::{end_of_lines, skip_carriage_return, ParseError};
+
 use crate::{Assignment, Pair, Section};
 
 /// Use `Text` as the `Input`; this parses `char` tokens.
 type Input<'a> = Pear<Text<'a>>;
 
+/// Parser results carry a [`PairErrorKind`] rather than `pear`'s own error so the kind of problem isn't lost.
+///
 /// See [`pear::macros::parser`] for details.
-type Result<'a, T> = pear::input::Result<T, Input<'a>>;
+type Result<'a, T> = std::result::Result<T, pear::error::ParseError<Span<'a>, PairErrorKind>>;
+
+/// How assignments with a start section after their end section are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RangeMode {
+    /// Reject inverted assignments like `6-4` with [`PairErrorKind::InvertedRange`].
+    #[default]
+    Strict,
+    /// Swap the sections of inverted assignments, so `6-4` is read as `4-6`.
+    Lenient,
+}
+
+/// The kind of problem found when parsing pairs of assignments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PairErrorKind {
+    /// An assignment starts after it ends, like `6-4`.
+    InvertedRange { start: Section, end: Section },
+    /// A section ID is too large to be a [`Section`].
+    SectionOverflow(String),
+    /// A section ID is missing, and the char found instead (if any).
+    MissingSection(Option<char>),
+    /// The separator between the sections of an assignment (`-`) or the assignments of a pair (`,`) is missing,
+    /// and the char found instead (if any).
+    MissingSeparator {
+        separator: char,
+        found: Option<char>,
+    },
+    /// A line has more than a pair of assignments.
+    TrailingGarbage(char),
+    /// Any other problem reported by the parser.
+    Expected(String),
+}
+
+/// Error produced when parsing pairs of assignments fails, with the location in the input where the problem was
+/// found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairError {
+    pub kind: PairErrorKind,
+    pub location: ParseError,
+}
+
+/// Describes the char found instead of what was expected.
+fn found(c: &Option<char>) -> String {
+    match c {
+        Some(c) => format!("{c:?}"),
+        None => "the end of the input".to_string(),
+    }
+}
+
+impl Display for PairErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PairErrorKind::InvertedRange { start, end } => write!(
+                f,
+                "expected the start section {start} to be no greater than the end section {end}"
+            ),
+            PairErrorKind::SectionOverflow(digits) => write!(
+                f,
+                "expected a section ID no greater than {} but found {digits}",
+                Section::MAX
+            ),
+            PairErrorKind::MissingSection(c) => {
+                write!(f, "expected a section ID but found {}", found(c))
+            }
+            PairErrorKind::MissingSeparator {
+                separator,
+                found: c,
+            } => {
+                write!(f, "expected {separator:?} but found {}", found(c))
+            }
+            PairErrorKind::TrailingGarbage(c) => {
+                write!(f, "expected the end of the line but found {c:?}")
+            }
+            PairErrorKind::Expected(expected) => write!(f, "{expected}"),
+        }
+    }
+}
+
+impl<T: Show, S: Show> From<Expected<T, S>> for PairErrorKind {
+    fn from(expected: Expected<T, S>) -> Self {
+        PairErrorKind::Expected(expected.to_string())
+    }
+}
+
+impl Display for PairError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.location)
+    }
+}
+
+impl Error for PairError {}
 
 /// Returns true if the char is a digit char (0, 1, 2, ..., 9)
 #[inline]
 fn is_num_char(&byte: &char) -> bool {
-    matches!(byte, '0'..='9')
+    byte.is_ascii_digit()
 }
 
 /// Parse section ID from numeric characters.
 #[parser]
 fn section<'a>(input: &mut Input<'a>) -> Result<'a, Section> {
-    take_some_while(is_num_char)?
-        .parse()
-        .or_else(|e| parse_error!("{}", e)?)
+    let digits = take_while(is_num_char)?;
+    if digits.is_empty() {
+        parse_error!(PairErrorKind::MissingSection(input.token()))?;
+    }
+    match digits.parse::<Section>() {
+        Ok(section) => section,
+        Err(_) => parse_error!(PairErrorKind::SectionOverflow(digits.to_string()))?,
+    }
+}
+
+/// Parse the `separator` char between sections or assignments.
+#[parser]
+fn separator<'a>(input: &mut Input<'a>, separator: char) -> Result<'a, ()> {
+    let found = input.token();
+    if found != Some(separator) {
+        parse_error!(PairErrorKind::MissingSeparator { separator, found })?;
+    }
+    input.eat(|c: &char| *c == separator);
 }
 
 /// Parse assigned section IDs.
+///
+/// Inverted assignments are reported at their start, rather than after their end section.
 #[parser]
-fn assignment<'a>(input: &mut Input<'a>) -> Result<'a, Assignment> {
+fn assignment<'a>(input: &mut Input<'a>, mode: RangeMode) -> Result<'a, Assignment> {
+    let assignment_start = input.mark(&ParserInfo {
+        name: "assignment",
+        raw: true,
+    });
     let start = section()?;
-    eat('-')?;
+    separator('-')?;
     let end = section()?;
-    Assignment::new(start, end)
+    match (Assignment::new(start, end), mode) {
+        (Some(assignment), _) => assignment,
+        (None, RangeMode::Lenient) => Assignment::new(end, start).expect("end is before start"),
+        (None, RangeMode::Strict) => {
+            input.rewind_to(assignment_start);
+            parse_error!(PairErrorKind::InvertedRange { start, end })?
+        }
+    }
 }
 
-/// Parse pair of assignments.
+/// Parse pair of assignments, and the end of the line.
+///
+/// The last line doesn't need to end with a newline.
 #[parser]
-fn pair<'a>(input: &mut Input<'a>) -> Result<'a, Pair> {
-    let first = assignment()?;
-    eat(',')?;
-    let second = assignment()?;
-    eat('\n')?;
+fn pair<'a>(input: &mut Input<'a>, mode: RangeMode) -> Result<'a, Pair> {
+    let first = assignment(mode)?;
+    separator(',')?;
+    let second = assignment(mode)?;
+    skip_carriage_return(input);
+    match input.token() {
+        Some('\n') => {
+            input.eat(|c: &char| *c == '\n');
+        }
+        Some(c) => parse_error!(PairErrorKind::TrailingGarbage(c))?,
+        None => {}
+    }
     Pair(first, second)
 }
 
-/// Parse list of pair of assignments.
+/// Parse list of pair of assignments, ignoring any empty lines at the end of the input.
+///
+/// `collect` checks for the end of the input by trying to parse it, and each failed attempt finds its line by
+/// scanning the input from the start, so the pairs are collected here instead to parse large lists in linear time.
 #[parser]
-fn pairs<'a>(input: &mut Input<'a>) -> Result<'a, Vec<Pair>> {
-    let pairs: Vec<Pair> = try_collect(pair)?;
+fn pairs<'a>(input: &mut Input<'a>, mode: RangeMode) -> Result<'a, Vec<Pair>> {
+    let mut pairs: Vec<Pair> = Vec::new();
+    while !end_of_lines(input) {
+        pairs.push(pair(mode)?);
+    }
     pairs
 }
 
-/// Parse list of paired assignments from `input`.
-pub(crate) fn parse_pairs(input: &str) -> Vec<Pair> {
-    parse!(pairs: Text::from(input)).expect("Failed to parse list of pair assignments from input")
+/// Parse list of paired assignments from `input`, rejecting inverted assignments.
+pub fn parse_pairs(input: &str) -> std::result::Result<Vec<Pair>, PairError> {
+    parse_pairs_with_mode(input, RangeMode::default())
+}
+
+/// Parse list of paired assignments from `input`, handling inverted assignments as the `mode` says.
+pub fn parse_pairs_with_mode(
+    input: &str,
+    mode: RangeMode,
+) -> std::result::Result<Vec<Pair>, PairError> {
+    let mut text = Input::new(input);
+    pairs(&mut text, mode).map_err(|e| PairError {
+        location: ParseError::from_pear(input, &e),
+        kind: e.error,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{parse_pairs, parse_pairs_with_mode, PairErrorKind, RangeMode};
+
+    #[test]
+    fn missing_separator_error_location() {
+        let error = parse_pairs("2-4,6-8\n2-3.4-5\n").err().unwrap();
+        assert_eq!(
+            (
+                error.location.offset,
+                error.location.line,
+                error.location.column
+            ),
+            (11, 2, 4)
+        );
+        assert_eq!(error.location.snippet, "2-3.4-5");
+        assert_eq!(
+            error.kind,
+            PairErrorKind::MissingSeparator {
+                separator: ',',
+                found: Some('.')
+            }
+        );
+
+        let error = parse_pairs("2-4,6-8\n2-3,4").err().unwrap();
+        assert_eq!(
+            error.kind,
+            PairErrorKind::MissingSeparator {
+                separator: '-',
+                found: None
+            }
+        );
+        assert_eq!(error.location.line, 2);
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        let pairs = parse_pairs("2-4,6-8\r\n2-3,4-5").unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[1].1.end(), 5);
+
+        assert_eq!(parse_pairs("2-4,6-8\n\n").unwrap().len(), 1);
+        assert_eq!(parse_pairs("2-4,6-8\r\n\r\n\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn inverted_ranges() {
+        let input = "2-4,6-8\n2-4,8-6\n";
+
+        let error = parse_pairs(input).err().unwrap();
+        assert_eq!(
+            error.kind,
+            PairErrorKind::InvertedRange { start: 8, end: 6 }
+        );
+        assert_eq!((error.location.line, error.location.column), (2, 5));
+
+        let pairs = parse_pairs_with_mode(input, RangeMode::Lenient).unwrap();
+        assert_eq!((pairs[1].1.start(), pairs[1].1.end()), (6, 8));
+    }
+
+    #[test]
+    fn overflowing_sections() {
+        let error = parse_pairs("2-4,6-8\n1-99999999999,3-4\n").err().unwrap();
+        assert_eq!(
+            error.kind,
+            PairErrorKind::SectionOverflow("99999999999".to_string())
+        );
+        assert_eq!(error.location.line, 2);
+        assert!(parse_pairs("0-4294967295,1-1").is_ok());
+    }
+
+    #[test]
+    fn trailing_garbage_and_missing_sections() {
+        let error = parse_pairs("2-4,6-8\n2-4,6-8,1-2\n").err().unwrap();
+        assert_eq!(error.kind, PairErrorKind::TrailingGarbage(','));
+        assert_eq!((error.location.line, error.location.column), (2, 8));
+
+        let error = parse_pairs("2-4,6-8\r\r\n2-4,6-8\n").err().unwrap();
+        assert_eq!(error.kind, PairErrorKind::TrailingGarbage('\r'));
+        assert_eq!((error.location.line, error.location.column), (1, 8));
+
+        let error = parse_pairs("2-4,6-8\n\n2-4,6-8\n").err().unwrap();
+        assert_eq!(error.kind, PairErrorKind::MissingSection(Some('\n')));
+        assert_eq!(error.location.line, 2);
+
+        let error = parse_pairs("2-4,x-8\n").err().unwrap();
+        assert_eq!(error.kind, PairErrorKind::MissingSection(Some('x')));
+    }
 }
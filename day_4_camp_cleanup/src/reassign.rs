@@ -0,0 +1,382 @@
+use std::{error::Error, fmt::Display, num::NonZeroU64};
+
+use crate::{Assignment, Coverage, Interval, Section};
+
+/// Limits on the sections each elf can be reassigned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Constraints {
+    /// Most sections an elf can be assigned, or `None` for no limit.
+    pub max_len: Option<NonZeroU64>,
+    /// Whether each elf is assigned a single run of consecutive sections, rather than several runs.
+    pub contiguous: bool,
+    /// Whether every elf keeps at least one section, even if that means sharing it with another elf, rather than
+    /// leaving elves beyond the number of sections without any.
+    pub assign_every_elf: bool,
+}
+
+/// New sections for each elf, indexed like the assignments they replace.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reassignment {
+    /// The runs of sections assigned to each elf, in order of their sections.
+    pub assignments: Vec<Vec<Assignment>>,
+}
+
+/// Error produced when there aren't enough elves to cover every section within the [`Constraints`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooFewElves {
+    pub needed: u64,
+    pub elves: usize,
+}
+
+impl Default for Constraints {
+    /// No limit on the number of sections, with each elf assigned a single run of them or none at all.
+    fn default() -> Self {
+        Constraints {
+            max_len: None,
+            contiguous: true,
+            assign_every_elf: false,
+        }
+    }
+}
+
+impl Reassignment {
+    /// Returns how many elves are assigned to each section.
+    pub fn coverage(&self) -> Coverage {
+        let runs: Vec<Assignment> = self.assignments.iter().flatten().copied().collect();
+        Coverage::new(&runs)
+    }
+
+    /// Returns the total redundancy of the new assignments (see [`Coverage::redundancy`]).
+    pub fn redundancy(&self) -> u64 {
+        self.coverage().redundancy()
+    }
+}
+
+/// Returns new assignments for the elves with the least total redundancy that still cover every section covered by
+/// their `assignments`, and no others, within the `constraints`.
+///
+/// Elves can share no sections as long as there are at least as many sections as elves. Any elves beyond that are
+/// left without sections, or, if every elf must keep a section, each keep the first section of their old assignment,
+/// which another elf has too. The sections are given out so that each elf's new sections are as near as possible to
+/// their old assignment.
+///
+/// Returns [`TooFewElves`] if the elves can't cover every section without exceeding the maximum length, e.g. if
+/// there are more runs of sections than elves when each elf is assigned a single run.
+pub fn reassign(
+    assignments: &[Assignment],
+    constraints: &Constraints,
+) -> Result<Reassignment, TooFewElves> {
+    let max_len = constraints.max_len.map_or(u64::MAX, NonZeroU64::get);
+
+    let runs = Coverage::new(assignments).covered();
+    let sections: u64 = runs.iter().map(Interval::len).sum();
+    let needed: u64 = if constraints.contiguous {
+        runs.iter().map(|run| run.len().div_ceil(max_len)).sum()
+    } else {
+        sections.div_ceil(max_len)
+    };
+    let elves = assignments.len();
+    if needed > elves as u64 {
+        return Err(TooFewElves { needed, elves });
+    }
+
+    // Elves that share no sections with each other, as many as there are elves or sections.
+    let sharing = sections.min(elves as u64);
+    let shares = if constraints.contiguous {
+        split_runs(&runs, sharing, max_len)
+    } else {
+        split_sections(&runs, sharing)
+    };
+
+    let mut reassigned = vec![Vec::new(); elves];
+    for (elf, runs) in nearest_elves(assignments, &shares).into_iter().zip(shares) {
+        reassigned[elf] = runs;
+    }
+    if constraints.assign_every_elf {
+        for (runs, assignment) in reassigned.iter_mut().zip(assignments) {
+            if runs.is_empty() {
+                runs.push(part(assignment, 0, 1));
+            }
+        }
+    }
+    Ok(Reassignment {
+        assignments: reassigned,
+    })
+}
+
+/// Returns twice the middle section of the sections from the `first` run to the `last`, to keep it whole.
+fn middle(first: &Interval, last: &Interval) -> u64 {
+    u64::from(first.start()) + u64::from(last.end())
+}
+
+/// Returns the elf given each of the `shares` of sections, which are in order of their sections, so that the total
+/// distance between the middle of each share and the middle of its elf's old assignment is the least.
+///
+/// The shares go to elves in order of the middles of their old assignments, since crossing two of them never makes
+/// them nearer, so this only chooses which elves are left without a share. It's a dynamic program over the shares
+/// and how many elves were skipped before each of them.
+fn nearest_elves(assignments: &[Assignment], shares: &[Vec<Interval>]) -> Vec<usize> {
+    let mut elves: Vec<usize> = (0..assignments.len()).collect();
+    elves.sort_by_key(|&elf| middle(&assignments[elf], &assignments[elf]));
+    let skippable = elves.len() - shares.len();
+    let distance = |share: usize, elf: usize| {
+        let runs = &shares[share];
+        let assignment = &assignments[elves[elf]];
+        middle(&runs[0], &runs[runs.len() - 1]).abs_diff(middle(assignment, assignment))
+    };
+
+    // The least total distance of the shares so far given to the elves so far, when `skip` elves are skipped.
+    let mut distances = vec![0; skippable + 1];
+    // Whether the least distance of each share and number of skipped elves skips the last of those elves.
+    let mut skips = vec![vec![false; skippable + 1]; shares.len()];
+    for (share, skips) in skips.iter_mut().enumerate() {
+        for skip in 0..=skippable {
+            let given = distances[skip] + distance(share, share + skip);
+            if skip > 0 && distances[skip - 1] < given {
+                distances[skip] = distances[skip - 1];
+                skips[skip] = true;
+            } else {
+                distances[skip] = given;
+            }
+        }
+    }
+
+    let mut given = vec![0; shares.len()];
+    let mut skip = skippable;
+    for share in (0..shares.len()).rev() {
+        while skips[share][skip] {
+            skip -= 1;
+        }
+        given[share] = elves[share + skip];
+    }
+    given
+}
+
+/// Returns the `len` sections of the `run` starting `offset` sections into it.
+fn part(run: &Interval, offset: u64, len: u64) -> Interval {
+    let start = u64::from(run.start()) + offset;
+    Interval::new(start as Section, (start + len - 1) as Section)
+        .expect("Parts are inside the run and nonempty")
+}
+
+/// Splits the `runs` into `shares` parts of consecutive sections, each no longer than `max_len`.
+///
+/// Each run is split into as few parts as fit `max_len`, then the runs with room for more parts are split further
+/// until there are `shares` parts, which must be between the fewest parts and the number of sections.
+fn split_runs(runs: &[Interval], shares: u64, max_len: u64) -> Vec<Vec<Interval>> {
+    let mut parts: Vec<u64> = runs.iter().map(|run| run.len().div_ceil(max_len)).collect();
+    let mut remaining = shares - parts.iter().sum::<u64>();
+    for (parts, run) in parts.iter_mut().zip(runs) {
+        let more = remaining.min(run.len() - *parts);
+        *parts += more;
+        remaining -= more;
+    }
+
+    let mut shares = Vec::new();
+    for (&parts, run) in parts.iter().zip(runs) {
+        // Parts differ in length by at most one section, so none is longer than the fewest parts would be.
+        let (len, longer) = (run.len() / parts, run.len() % parts);
+        let mut offset = 0;
+        for index in 0..parts {
+            let len = len + u64::from(index < longer);
+            shares.push(vec![part(run, offset, len)]);
+            offset += len;
+        }
+    }
+    shares
+}
+
+/// Splits the sections of the `runs` into `shares` nearly equal shares, which may each span several runs.
+fn split_sections(runs: &[Interval], shares: u64) -> Vec<Vec<Interval>> {
+    let sections: u64 = runs.iter().map(Interval::len).sum();
+    let mut runs = runs.iter();
+    let mut run = runs.next();
+    let mut offset = 0;
+
+    let mut split = Vec::new();
+    for index in 0..shares {
+        let mut quota = sections / shares + u64::from(index < sections % shares);
+        let mut share = Vec::new();
+        while quota > 0 {
+            let current = run.expect("Shares have as many sections as the runs");
+            let len = quota.min(current.len() - offset);
+            share.push(part(current, offset, len));
+            quota -= len;
+            offset += len;
+            if offset == current.len() {
+                run = runs.next();
+                offset = 0;
+            }
+        }
+        split.push(share);
+    }
+    split
+}
+
+impl Display for Reassignment {
+    /// Lists each elf's new sections.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (elf, runs) in self.assignments.iter().enumerate() {
+            if runs.is_empty() {
+                writeln!(f, "Elf {elf}: no sections")?;
+                continue;
+            }
+            let runs: Vec<String> = runs.iter().map(Interval::to_string).collect();
+            writeln!(f, "Elf {elf}: {}", runs.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TooFewElves {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} elves are needed to cover every section within the constraints, but there are only {}",
+            self.needed, self.elves
+        )
+    }
+}
+
+impl Error for TooFewElves {}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU64;
+
+    use crate::{
+        assignments, parse_pairs,
+        reassign::{reassign, Constraints, TooFewElves},
+        Assignment, Coverage, Interval, Section,
+    };
+
+    fn interval(start: Section, end: Section) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    /// Checks that the reassignment of the `assignments` covers the same sections within the `constraints`, with the
+    /// least redundancy.
+    fn check(assignments: &[Assignment], constraints: &Constraints) {
+        let reassignment = reassign(assignments, constraints).unwrap();
+        let covered = Coverage::new(assignments).covered();
+        assert_eq!(reassignment.coverage().covered(), covered);
+
+        assert_eq!(reassignment.assignments.len(), assignments.len());
+        for runs in &reassignment.assignments {
+            assert!(!constraints.assign_every_elf || !runs.is_empty());
+            if constraints.contiguous {
+                assert!(runs.len() <= 1);
+            }
+            let len: u64 = runs.iter().map(Interval::len).sum();
+            assert!(len <= constraints.max_len.map_or(u64::MAX, NonZeroU64::get));
+        }
+
+        let sections: u64 = covered.iter().map(Interval::len).sum();
+        let redundancy = if constraints.assign_every_elf {
+            (assignments.len() as u64).saturating_sub(sections)
+        } else {
+            0
+        };
+        assert_eq!(reassignment.redundancy(), redundancy);
+    }
+
+    #[test]
+    fn reassign_example() {
+        let pairs = parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").unwrap();
+        let assignments = assignments(&pairs);
+        assert_eq!(Coverage::new(&assignments).redundancy(), 34);
+
+        // 12 elves cover sections 2-9, so 4 of them are left without a section.
+        let reassignment = reassign(&assignments, &Constraints::default()).unwrap();
+        assert_eq!(reassignment.redundancy(), 0);
+        // Each elf with a section keeps one of their old ones: elf 2 (2-3) the first, elf 0 (2-4) the next, and
+        // elf 5 (7-9) the last.
+        assert_eq!(reassignment.assignments[2], vec![interval(2, 2)]);
+        assert_eq!(reassignment.assignments[0], vec![interval(3, 3)]);
+        assert_eq!(reassignment.assignments[5], vec![interval(9, 9)]);
+        for (runs, assignment) in reassignment.assignments.iter().zip(&assignments) {
+            assert!(runs.iter().all(|run| assignment.contains_interval(run)));
+        }
+        let empty = reassignment
+            .assignments
+            .iter()
+            .filter(|runs| runs.is_empty())
+            .count();
+        assert_eq!(empty, 4);
+
+        // If every elf has to keep a section, 4 of them have to share one.
+        let constraints = Constraints {
+            assign_every_elf: true,
+            ..Constraints::default()
+        };
+        assert_eq!(
+            reassign(&assignments, &constraints).unwrap().redundancy(),
+            4
+        );
+
+        for max_len in 1..=8 {
+            for contiguous in [true, false] {
+                for assign_every_elf in [true, false] {
+                    let constraints = Constraints {
+                        max_len: NonZeroU64::new(max_len),
+                        contiguous,
+                        assign_every_elf,
+                    };
+                    check(&assignments, &constraints);
+                    // Three elves can cover the two runs of sections 2-4 and 6-8 when they can each take three.
+                    if max_len >= 3 {
+                        check(&assignments[..3], &constraints);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reassign_runs() {
+        let assignments = [
+            interval(1, 4),
+            interval(6, 9),
+            interval(11, 14),
+            interval(1, 4),
+        ];
+        let mut constraints = Constraints {
+            max_len: NonZeroU64::new(3),
+            contiguous: true,
+            assign_every_elf: false,
+        };
+
+        // Each run needs two elves of up to three sections, but elves with several runs can cover them.
+        assert_eq!(
+            reassign(&assignments, &constraints),
+            Err(TooFewElves {
+                needed: 6,
+                elves: 4
+            })
+        );
+        constraints.contiguous = false;
+        check(&assignments, &constraints);
+        let reassignment = reassign(&assignments, &constraints).unwrap();
+        assert_eq!(
+            reassignment.assignments[3],
+            vec![interval(4, 4), interval(6, 7)]
+        );
+        assert_eq!(reassignment.redundancy(), 0);
+
+        constraints.max_len = NonZeroU64::new(2);
+        assert_eq!(
+            reassign(&assignments, &constraints),
+            Err(TooFewElves {
+                needed: 6,
+                elves: 4
+            })
+        );
+
+        let assignments = [interval(0, Section::MAX), interval(5, 5)];
+        check(&assignments, &Constraints::default());
+        assert!(reassign(&[], &Constraints::default())
+            .unwrap()
+            .assignments
+            .is_empty());
+    }
+}
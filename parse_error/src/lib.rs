@@ -0,0 +1,132 @@
+use std::fmt::{self, Display};
+
+use pear::input::{Extent, Span};
+
+pub use lines::{end_of_lines, skip_carriage_return};
+
+mod lines;
+
+/// Error produced when parsing puzzle input fails, located at the position in the input where parsing stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the input where parsing stopped.
+    pub offset: usize,
+    /// Line number (starting from 1) where parsing stopped.
+    pub line: usize,
+    /// Column number in chars (starting from 1) where parsing stopped.
+    pub column: usize,
+    /// The full line of input where parsing stopped.
+    pub snippet: String,
+    /// Description of what the parser expected to find.
+    pub expected: String,
+}
+
+/// Context of a `pear` input that knows the byte offset the parser was at when it produced the context.
+pub trait Offset {
+    /// Returns the byte offset into the input.
+    fn offset(&self) -> usize;
+}
+
+impl Offset for Span<'_> {
+    fn offset(&self) -> usize {
+        self.end.2
+    }
+}
+
+impl<T> Offset for Extent<T> {
+    fn offset(&self) -> usize {
+        self.end
+    }
+}
+
+impl ParseError {
+    /// Construct a `ParseError` located at byte `offset` into `input`.
+    ///
+    /// An `offset` past the end of the `input` is clamped to the end.
+    pub fn new(input: &str, offset: usize, expected: impl Into<String>) -> ParseError {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |newline| offset + newline);
+
+        ParseError {
+            offset,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Construct a `ParseError` from an error produced by a `pear` parser over `input`.
+    pub fn from_pear<C: Offset, E: Display>(
+        input: &str,
+        error: &pear::error::ParseError<C, E>,
+    ) -> ParseError {
+        ParseError::new(input, error.info.context.offset(), error.error.to_string())
+    }
+}
+
+/// Renders the error like a compiler diagnostic with a caret pointing at the column where parsing stopped.
+///
+/// For example:
+/// ```text
+/// error: expected token ',' but found '.'
+///  --> 2:4
+///   |
+/// 2 | 2-3.4-5
+///   |    ^
+/// ```
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: {}", self.expected)?;
+        writeln!(f, "{gutter}--> {}:{}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use crate::ParseError;
+
+    #[test]
+    fn locates_offset() {
+        let error = ParseError::new("2-4,6-8\n2-3.4-5\n", 11, "expected ','");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 4);
+        assert_eq!(error.snippet, "2-3.4-5");
+    }
+
+    #[test]
+    fn locates_end_of_input() {
+        let error = ParseError::new("A Y\nB", 5, "expected ' '");
+        assert_eq!((error.offset, error.line, error.column), (5, 2, 2));
+        assert_eq!(error.snippet, "B");
+    }
+
+    #[test]
+    fn renders_caret() {
+        let error = ParseError::new("2-4,6-8\n2-3.4-5\n", 11, "expected token ','");
+        assert_eq!(
+            error.to_string(),
+            "\
+error: expected token ','
+ --> 2:4
+  |
+2 | 2-3.4-5
+  |    ^"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use pear::input::{Input, ParserInfo, Pear, Rewind, Text};
+
+/// Returns true if the char is the \r of a \r\n line ending
+#[inline]
+fn is_carriage_return_char(&c: &char) -> bool {
+    c == '\r'
+}
+
+/// Returns true if the char is part of a line ending
+#[inline]
+fn is_line_end_char(&c: &char) -> bool {
+    c == '\r' || c == '\n'
+}
+
+/// Eats the \r of a \r\n line ending, or a \r ending the `input`, so a parser can go on to expect a \n or the end of
+/// the input.
+///
+/// Any other \r is left in the input, so it's reported where it is rather than at the char after it.
+pub fn skip_carriage_return(input: &mut Pear<Text<'_>>) {
+    if input.peek_slice(2, |&slice: &&str| slice == "\r\n")
+        || (input.peek(is_carriage_return_char) && !input.has(2))
+    {
+        input.eat(is_carriage_return_char);
+    }
+}
+
+/// Eats any empty lines ending the `input`, and returns whether the input has ended.
+///
+/// Empty lines followed by more lines are left in the input, to be reported by the parser of those lines.
+pub fn end_of_lines(input: &mut Pear<Text<'_>>) -> bool {
+    let start = input.mark(&ParserInfo {
+        name: "end_of_lines",
+        raw: true,
+    });
+    input.skip(is_line_end_char);
+    if input.has(1) {
+        input.rewind_to(start);
+        false
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pear::input::{Input, Pear, Text};
+
+    use crate::{end_of_lines, skip_carriage_return};
+
+    #[test]
+    fn skips_only_line_ending_carriage_returns() {
+        for (text, rest) in [
+            ("\r\nA", Some('\n')),
+            ("\r", None),
+            ("\r\r\n", Some('\r')),
+            ("\rA", Some('\r')),
+        ] {
+            let mut input: Pear<Text> = Pear::new(text);
+            skip_carriage_return(&mut input);
+            assert_eq!(input.token(), rest, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn ends_only_at_trailing_empty_lines() {
+        let mut input: Pear<Text> = Pear::new("\r\n\n");
+        assert!(end_of_lines(&mut input));
+
+        let mut input: Pear<Text> = Pear::new("\n\nA");
+        assert!(!end_of_lines(&mut input));
+        assert_eq!(input.token(), Some('\n'));
+    }
+}